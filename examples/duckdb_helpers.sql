@@ -38,6 +38,12 @@ CREATE OR REPLACE MACRO vortex_to_timestamptz(micros) AS (
 -- INTERVAL CONVERSION MACROS
 -- ========================================================================
 
+-- Convert an oracle2vortex.interval value (struct {months, days, nanos}) to DuckDB INTERVAL
+CREATE OR REPLACE MACRO vortex_to_interval(iv) AS (
+    to_months(iv.months) + to_days(iv.days) + to_microseconds(iv.nanos // 1000)
+);
+
+-- Files written before the interval extension type stored bare integers:
 -- Convert Vortex INTERVAL DAY TO SECOND (I64 microseconds) to DuckDB INTERVAL
 CREATE OR REPLACE MACRO vortex_to_interval_ds(micros) AS (
     INTERVAL (micros / 1000000.0) SECONDS
@@ -1,18 +1,44 @@
 use clap::Parser;
 use std::path::PathBuf;
 
+use crate::columns::RenameCase;
+use crate::detection::OracleDateAs;
+use crate::lobs::LobMode;
+use crate::partitions::PartitionOutput;
+use crate::raw::Raw16As;
+use crate::vortex_writer::SchemaDrift;
+
 #[derive(Parser, Debug)]
 #[command(name = "oracle2vortex")]
 #[command(about = "Export Oracle tables to Vortex format via SQLcl streaming", long_about = None)]
 pub struct CliArgs {
     /// Path to SQL query file
-    #[arg(short = 'f', long)]
-    pub sql_file: PathBuf,
+    #[arg(short = 'f', long, required_unless_present = "table", conflicts_with = "table")]
+    pub sql_file: Option<PathBuf>,
 
-    /// Output Vortex file path
+    /// Output Vortex file path (a directory with --partition-output per-partition)
     #[arg(short = 'o', long)]
     pub output: PathBuf,
 
+    /// Export a table (TABLE or OWNER.TABLE) instead of a query, one unit of work
+    /// per partition or subpartition (from ALL_TAB_PARTITIONS / ALL_TAB_SUBPARTITIONS)
+    #[arg(long, value_name = "TABLE")]
+    pub table: Option<String>,
+
+    /// With --table: merged (one output file) or per-partition (one file per
+    /// partition in the output directory)
+    #[arg(long, value_enum, default_value = "merged")]
+    pub partition_output: PartitionOutput,
+
+    /// With --table: number of partitions exported at the same time
+    #[arg(long, default_value = "4")]
+    pub parallel: usize,
+
+    /// With --table: skip partitions completed by an interrupted run, reading
+    /// the others as of the same SCN
+    #[arg(long, default_value = "false")]
+    pub resume: bool,
+
     /// Complete Oracle connection string (user/password@connect_identifier)
     /// Example: hr/mypass@//localhost:1521/ORCL
     /// When provided, --user, --password, --host, --port, and --sid are ignored
@@ -48,34 +74,210 @@ pub struct CliArgs {
     pub batch_size: usize,
 
     /// Auto-batch mode: split query into batches of N rows (0 = disabled, query runs as-is)
-    /// When enabled, wraps query with OFFSET/FETCH and executes multiple times
+    /// When enabled, adds OFFSET/FETCH after the final ORDER BY of the query (an unordered
+    /// query is wrapped with a warning, refused with --strict) and executes it multiple times
     #[arg(long, default_value = "0")]
     pub auto_batch_rows: usize,
 
     /// Skip Oracle LOB types (CLOB, BLOB, NCLOB) - exclude them from the output
+    /// Shorthand for --lob-mode skip
     #[arg(long, default_value = "false")]
     pub skip_lobs: bool,
 
+    /// Handling of LOB columns: keep, skip, truncate:N (keep at most N bytes),
+    /// or external:N (if any value is over N bytes, write the values to <output>.lobs/<sha256>
+    /// and keep {path, size, sha256} in the column)
+    /// LOB columns are identified by their Oracle type
+    #[arg(long, default_value = "keep", value_parser = LobMode::parse)]
+    pub lob_mode: LobMode,
+
+    /// LOB handling for one column (COLUMN=mode), can be repeated
+    #[arg(long = "lob", value_name = "COLUMN=MODE")]
+    pub lob_columns: Vec<String>,
+
+    /// SQLcl SET LONG: maximum bytes fetched for each CLOB/LONG value
+    #[arg(long, default_value = "2000000000")]
+    pub long: u64,
+
+    /// SQLcl SET LONGCHUNKSIZE: bytes fetched per chunk for CLOB/LONG values
+    #[arg(long, default_value = "2000000000")]
+    pub long_chunk_size: u64,
+
+    /// SQLcl SET LOBPREFETCH: LOB bytes prefetched with each row
+    #[arg(long)]
+    pub lob_prefetch: Option<u64>,
+
+    /// Compare LOB values with DBMS_LOB.GETLENGTH and warn about truncated values
+    /// Runs an extra describe query and adds one length column per LOB to the query
+    #[arg(long, default_value = "false")]
+    pub verify_lob_lengths: bool,
+
+    /// SQL file run in every SQLcl session after the mandatory settings
+    /// (e.g. ALTER SESSION SET CURRENT_SCHEMA, NLS_SORT, EDITION)
+    /// Statements that change the formats or SET options the export relies on are skipped with a warning
+    #[arg(long, value_name = "FILE")]
+    pub session_init: Option<PathBuf>,
+
+    /// Read the data as of this SCN (flashback query; needs EXECUTE on DBMS_FLASHBACK)
+    #[arg(long, value_name = "SCN", conflicts_with = "as_of_timestamp")]
+    pub as_of_scn: Option<u64>,
+
+    /// Read the data as of this time, in database time (YYYY-MM-DD HH:MI:SS)
+    /// Converted once with TIMESTAMP_TO_SCN, so every query sees the same SCN
+    #[arg(long, value_name = "TIMESTAMP")]
+    pub as_of_timestamp: Option<String>,
+
+    /// Do not pin auto-batches and partitions to the SCN captured at the start of the export
+    /// (without this flag, an SCN that cannot be read fails the export)
+    #[arg(long, default_value = "false")]
+    pub no_snapshot: bool,
+
     /// Use Oracle Thick driver (JDBC/OCI) instead of Thin driver
     /// Enables features like connection pooling, advanced security, and better performance
     #[arg(long, default_value = "false")]
     pub thick: bool,
+
+    /// Schema file (JSON or TOML) mapping column names to Vortex types
+    /// Types: utf8, i32, i64, f32, f64, decimal(p,s), date, timestamp(unit, tz), binary, bool
+    /// Append "not null" to a type to make the column non-nullable
+    #[arg(long)]
+    pub schema_file: Option<PathBuf>,
+
+    /// Force the type of a column (COLUMN=type), can be repeated
+    /// Example: --cast ZIP_CODE=utf8 --cast AMOUNT="decimal(12,2)"
+    #[arg(long = "cast", value_name = "COLUMN=TYPE")]
+    pub casts: Vec<String>,
+
+    /// Type-detection heuristics to apply to string values (comma-separated)
+    /// Values: all, none, dates, timestamps, intervals, binary, json (default: all)
+    #[arg(long, value_delimiter = ',', value_name = "HEURISTICS")]
+    pub detect: Vec<String>,
+
+    /// Do not convert hex-looking strings to Binary
+    #[arg(long, default_value = "false")]
+    pub no_detect_binary: bool,
+
+    /// Disable all type detection: strings stay strings
+    #[arg(long, default_value = "false")]
+    pub strings_only: bool,
+
+    /// Exempt a column from type detection, can be repeated
+    #[arg(long = "no-detect-column", value_name = "COLUMN")]
+    pub no_detect_columns: Vec<String>,
+
+    /// Vortex type for RAW(16) columns (SYS_GUID() keys): binary (16 fixed bytes),
+    /// uuid (arrow.uuid extension) or text (canonical UUID string)
+    #[arg(long = "raw16-as", value_enum, default_value = "binary")]
+    pub raw16_as: Raw16As,
+
+    /// Keep every integer column I64 and every float column F64 instead of
+    /// narrowing them to the NUMBER precision or BINARY_FLOAT
+    #[arg(long, default_value = "false")]
+    pub wide_numbers: bool,
+
+    /// Also narrow integer columns without a declared precision to the range
+    /// of their values; the column type may then differ between runs or batches
+    #[arg(long, default_value = "false", conflicts_with = "wide_numbers")]
+    pub narrow_by_values: bool,
+
+    /// Convert single-character flag columns to Bool when all their values follow
+    /// these TRUE/FALSE pairs (comma-separated, default: Y/N,1/0,T/F)
+    #[arg(long, num_args = 0..=1, default_missing_value = crate::flags::DEFAULT_FLAG_PAIRS, value_name = "PAIRS")]
+    pub bool_flags: Option<String>,
+
+    /// Convert a column to Bool (COLUMN or COLUMN=PAIRS), can be repeated
+    /// Values that follow no pair are reported and stored as null
+    /// Example: --bool-flag ACTIVE --bool-flag STATUS=O/C
+    #[arg(long = "bool-flag", value_name = "COLUMN[=PAIRS]")]
+    pub bool_flag_columns: Vec<String>,
+
+    /// Write every value that could not be converted to this NDJSON file
+    /// (one line per value: row, column, value, target_type, reason)
+    #[arg(long, value_name = "FILE")]
+    pub rejects: Option<PathBuf>,
+
+    /// Abort the export once more than N values have been rejected
+    #[arg(long, value_name = "N")]
+    pub max_rejects: Option<usize>,
+
+    /// Fail on any lossy conversion (unparseable value, float or timestamp
+    /// precision loss, time zone normalisation, dropped DATE time, LOB truncation)
+    /// Turns on --verify-lob-lengths so that LOBs cut by SQLcl are detected
+    #[arg(long, default_value = "false")]
+    pub strict: bool,
+
+    /// Columns to place first in the output, in this order (comma-separated)
+    /// Remaining columns keep the SELECT order
+    #[arg(long, value_delimiter = ',', value_name = "COLUMNS")]
+    pub column_order: Vec<String>,
+
+    /// Rename a column in the output (OLD=new), can be repeated
+    /// Takes precedence over --rename-case
+    #[arg(long = "rename", value_name = "OLD=NEW")]
+    pub renames: Vec<String>,
+
+    /// Only export columns matching these patterns (* and ? wildcards, comma-separated)
+    #[arg(long, value_delimiter = ',', value_name = "PATTERNS")]
+    pub include: Vec<String>,
+
+    /// Do not export columns matching these patterns (* and ? wildcards, comma-separated)
+    #[arg(long, value_delimiter = ',', value_name = "PATTERNS")]
+    pub exclude: Vec<String>,
+
+    /// Case conversion for output column names
+    #[arg(long, value_enum, default_value = "preserve")]
+    pub rename_case: RenameCase,
+
+    /// Vortex type for Oracle DATE columns, which always carry a time of day:
+    /// date (drop the time), timestamp, or auto (date when every value is at midnight)
+    #[arg(long, value_enum, default_value = "timestamp")]
+    pub oracle_date_as: OracleDateAs,
+
+    /// Add a <column>_tz column with the original offset or region of each
+    /// TIMESTAMP WITH TIME ZONE value (values themselves are stored in UTC)
+    #[arg(long, default_value = "false")]
+    pub time_zone_columns: bool,
+
+    /// Write JSON columns (Oracle JSON type, or values that are all JSON objects
+    /// or arrays) as nested structs and lists instead of JSON text
+    #[arg(long, default_value = "false")]
+    pub nested_json: bool,
+
+    /// What to do when columns appear or disappear between auto-batches
+    #[arg(long, value_enum, default_value = "warn")]
+    pub on_schema_drift: SchemaDrift,
 }
 
 impl CliArgs {
     pub fn validate(&self) -> anyhow::Result<()> {
-        if !self.sql_file.exists() {
-            anyhow::bail!("SQL file does not exist: {:?}", self.sql_file);
+        if let Some(ref sql_file) = self.sql_file {
+            if !sql_file.exists() {
+                anyhow::bail!("SQL file does not exist: {:?}", sql_file);
+            }
+
+            if !sql_file.is_file() {
+                anyhow::bail!("SQL file path is not a file: {:?}", sql_file);
+            }
         }
 
-        if !self.sql_file.is_file() {
-            anyhow::bail!("SQL file path is not a file: {:?}", self.sql_file);
+        if self.parallel == 0 {
+            anyhow::bail!("--parallel must be at least 1");
         }
 
-        if self.output.exists() {
+        if self.table.is_some() && self.partition_output == PartitionOutput::PerPartition {
+            if self.output.is_file() {
+                anyhow::bail!("Output must be a directory with --partition-output per-partition: {:?}", self.output);
+            }
+        } else if self.output.exists() {
             tracing::warn!("Output file already exists and will be overwritten: {:?}", self.output);
         }
 
+        if let Some(ref schema_file) = self.schema_file {
+            if !schema_file.is_file() {
+                anyhow::bail!("Schema file does not exist: {:?}", schema_file);
+            }
+        }
+
         // Validate that we have either connect_string OR all individual components
         if self.connect_string.is_none() && 
            (self.user.is_none() || self.password.is_none() || self.host.is_none() || self.sid.is_none()) {
@@ -0,0 +1,232 @@
+use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
+
+/// Case conversion applied to output column names
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum RenameCase {
+    /// Keep names as SQLcl reports them
+    #[default]
+    Preserve,
+    /// Lower-case names
+    Lower,
+    /// snake_case names (`CustomerId` and `CUSTOMER_ID` both become `customer_id`)
+    Snake,
+}
+
+impl RenameCase {
+    pub fn apply(&self, name: &str) -> String {
+        match self {
+            RenameCase::Preserve => name.to_string(),
+            RenameCase::Lower => name.to_lowercase(),
+            RenameCase::Snake => to_snake_case(name),
+        }
+    }
+}
+
+/// Convert a column name to snake_case
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::with_capacity(name.len() + 4);
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c == ' ' || c == '-' || c == '_' {
+            if !out.is_empty() && !out.ends_with('_') {
+                out.push('_');
+            }
+            continue;
+        }
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).map(|n| n.is_lowercase()).unwrap_or(false);
+            // Word boundary: fooBar, or the last capital of an acronym (HTTPServer)
+            if (prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_is_lower))
+                && !out.ends_with('_')
+            {
+                out.push('_');
+            }
+        }
+        out.extend(c.to_lowercase());
+    }
+
+    out.trim_end_matches('_').to_string()
+}
+
+/// Match a column name against a pattern with `*` and `?` wildcards (case-insensitive)
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+
+    // Iterative wildcard matching with backtracking on the last `*`
+    let (mut p, mut n) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            p = star_p + 1;
+            n = star_n + 1;
+            star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Selection, order and names of the columns written to the Vortex file
+///
+/// Column names are matched case-insensitively against the query columns.
+#[derive(Debug, Clone, Default)]
+pub struct ColumnLayout {
+    order: Vec<String>,
+    renames: HashMap<String, String>,
+    include: Vec<String>,
+    exclude: Vec<String>,
+    case: RenameCase,
+}
+
+impl ColumnLayout {
+    /// Build a layout from CLI options
+    ///
+    /// # Arguments
+    /// * `order` - Columns to place first, in this order; other columns follow in query order
+    /// * `renames` - `OLD=new` pairs, which take precedence over `case`
+    /// * `include` - Patterns of columns to keep (all columns if empty)
+    /// * `exclude` - Patterns of columns to drop, applied after `include`
+    /// * `case` - Case conversion for names not explicitly renamed
+    pub fn from_args(
+        order: &[String],
+        renames: &[String],
+        include: &[String],
+        exclude: &[String],
+        case: RenameCase,
+    ) -> Result<Self> {
+        let mut parsed_renames = HashMap::new();
+        for rename in renames {
+            let (old, new) = rename
+                .split_once('=')
+                .with_context(|| format!("Invalid --rename '{}' (expected OLD=new)", rename))?;
+            if new.trim().is_empty() {
+                anyhow::bail!("Invalid --rename '{}': new name is empty", rename);
+            }
+            parsed_renames.insert(old.trim().to_lowercase(), new.trim().to_string());
+        }
+
+        Ok(Self {
+            order: order.iter().map(|c| c.trim().to_lowercase()).collect(),
+            renames: parsed_renames,
+            include: include.iter().map(|p| p.trim().to_string()).collect(),
+            exclude: exclude.iter().map(|p| p.trim().to_string()).collect(),
+            case,
+        })
+    }
+
+    /// Whether a column is kept by the include/exclude patterns
+    pub fn is_selected(&self, column: &str) -> bool {
+        let included = self.include.is_empty() || self.include.iter().any(|p| matches_pattern(p, column));
+        included && !self.exclude.iter().any(|p| matches_pattern(p, column))
+    }
+
+    /// Arrange source columns (given in query order) into `(source, output name)` pairs
+    ///
+    /// Columns removed by the include/exclude patterns are left out.
+    pub fn arrange(&self, columns: &[String]) -> Result<Vec<(String, String)>> {
+        let selected: Vec<&String> = columns.iter().filter(|c| self.is_selected(c)).collect();
+
+        let mut arranged: Vec<&String> = Vec::with_capacity(selected.len());
+
+        for wanted in &self.order {
+            match selected.iter().find(|c| c.to_lowercase() == *wanted) {
+                Some(column) if !arranged.contains(column) => arranged.push(column),
+                Some(_) => {}
+                None => tracing::warn!("--column-order: column '{}' not found in the selected columns", wanted),
+            }
+        }
+        for column in selected {
+            if !arranged.contains(&column) {
+                arranged.push(column);
+            }
+        }
+
+        let mut seen = HashSet::new();
+        arranged
+            .into_iter()
+            .map(|column| {
+                let output = self
+                    .renames
+                    .get(&column.to_lowercase())
+                    .cloned()
+                    .unwrap_or_else(|| self.case.apply(column));
+                if !seen.insert(output.clone()) {
+                    anyhow::bail!("Duplicate output column name '{}'", output);
+                }
+                Ok((column.clone(), output))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(cols: &[&str]) -> Vec<String> {
+        cols.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn test_arrange_order_and_rename() {
+        let layout = ColumnLayout::from_args(&names(&["NAME"]), &names(&["id=customer_id"]), &[], &[], RenameCase::Preserve)
+            .unwrap();
+        let arranged = layout.arrange(&names(&["id", "email", "name"])).unwrap();
+        assert_eq!(
+            arranged,
+            vec![
+                ("name".to_string(), "name".to_string()),
+                ("id".to_string(), "customer_id".to_string()),
+                ("email".to_string(), "email".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_arrange_rejects_duplicate_names() {
+        let layout = ColumnLayout::from_args(&[], &names(&["a=b"]), &[], &[], RenameCase::Preserve).unwrap();
+        assert!(layout.arrange(&names(&["a", "b"])).is_err());
+    }
+
+    #[test]
+    fn test_include_exclude_patterns() {
+        let layout = ColumnLayout::from_args(
+            &[],
+            &[],
+            &names(&["CUST_*", "ID"]),
+            &names(&["*_SSN", "cust_?mail"]),
+            RenameCase::Preserve,
+        )
+        .unwrap();
+        let arranged = layout
+            .arrange(&names(&["id", "cust_name", "cust_ssn", "cust_email", "notes"]))
+            .unwrap();
+        let kept: Vec<&str> = arranged.iter().map(|(source, _)| source.as_str()).collect();
+        assert_eq!(kept, vec!["id", "cust_name"]);
+    }
+
+    #[test]
+    fn test_rename_case() {
+        assert_eq!(RenameCase::Snake.apply("CUSTOMER_ID"), "customer_id");
+        assert_eq!(RenameCase::Snake.apply("CustomerId"), "customer_id");
+        assert_eq!(RenameCase::Snake.apply("HTTPServer2Name"), "http_server2_name");
+        assert_eq!(RenameCase::Lower.apply("Order Date"), "order date");
+
+        let layout =
+            ColumnLayout::from_args(&[], &names(&["ID=Key"]), &[], &[], RenameCase::Snake).unwrap();
+        let arranged = layout.arrange(&names(&["ID", "OrderDate"])).unwrap();
+        assert_eq!(arranged[0].1, "Key");
+        assert_eq!(arranged[1].1, "order_date");
+    }
+}
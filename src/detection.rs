@@ -0,0 +1,127 @@
+use anyhow::Result;
+use std::collections::HashSet;
+
+/// Type-detection heuristics applied to string values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Heuristics {
+    /// `YYYY-MM-DD` strings become `vortex.date`
+    pub dates: bool,
+    /// ISO 8601 timestamps (with or without time zone) become `vortex.timestamp`
+    pub timestamps: bool,
+    /// Oracle INTERVAL literals become `oracle2vortex.interval` values
+    pub intervals: bool,
+    /// Even-length hex strings of 8+ characters become Binary
+    pub binary: bool,
+    /// Strings starting with `{` or `[` are validated as JSON
+    pub json: bool,
+}
+
+impl Heuristics {
+    pub const ALL: Self = Self {
+        dates: true,
+        timestamps: true,
+        intervals: true,
+        binary: true,
+        json: true,
+    };
+
+    /// Strings stay strings
+    pub const NONE: Self = Self {
+        dates: false,
+        timestamps: false,
+        intervals: false,
+        binary: false,
+        json: false,
+    };
+}
+
+/// Vortex type for Oracle DATE columns
+///
+/// Oracle DATE always carries a time of day (the session's `NLS_DATE_FORMAT`
+/// emits `YYYY-MM-DDTHH:MI:SS`), so it maps to a timestamp unless told otherwise.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OracleDateAs {
+    /// `vortex.date`: the time of day is dropped
+    Date,
+    /// `vortex.timestamp`
+    #[default]
+    Timestamp,
+    /// `vortex.date` when every value of the column is at midnight, otherwise `vortex.timestamp`
+    Auto,
+}
+
+/// Which heuristics `VortexWriter` may use, with per-column exemptions
+#[derive(Debug, Clone)]
+pub struct DetectionPolicy {
+    heuristics: Heuristics,
+    exempt_columns: HashSet<String>,
+}
+
+impl Default for DetectionPolicy {
+    fn default() -> Self {
+        Self {
+            heuristics: Heuristics::ALL,
+            exempt_columns: HashSet::new(),
+        }
+    }
+}
+
+impl DetectionPolicy {
+    /// Build a policy from CLI options
+    ///
+    /// # Arguments
+    /// * `detect` - Heuristics to enable (`all`, `none`, `dates`, `timestamps`, `intervals`,
+    ///   `binary`, `json`); empty means all
+    /// * `no_detect_binary` - Disable hex → Binary detection
+    /// * `strings_only` - Disable every heuristic
+    /// * `exempt_columns` - Columns that never go through detection
+    pub fn from_args(
+        detect: &[String],
+        no_detect_binary: bool,
+        strings_only: bool,
+        exempt_columns: &[String],
+    ) -> Result<Self> {
+        let mut heuristics = if detect.is_empty() { Heuristics::ALL } else { Heuristics::NONE };
+
+        for name in detect {
+            match name.trim().to_lowercase().as_str() {
+                "all" => heuristics = Heuristics::ALL,
+                "none" => heuristics = Heuristics::NONE,
+                "dates" => heuristics.dates = true,
+                "timestamps" => heuristics.timestamps = true,
+                "intervals" => heuristics.intervals = true,
+                "binary" => heuristics.binary = true,
+                "json" => heuristics.json = true,
+                other => anyhow::bail!(
+                    "Unknown detection heuristic '{}' (expected all, none, dates, timestamps, intervals, binary or json)",
+                    other
+                ),
+            }
+        }
+
+        if no_detect_binary {
+            heuristics.binary = false;
+        }
+        if strings_only {
+            heuristics = Heuristics::NONE;
+        }
+
+        Ok(Self {
+            heuristics,
+            exempt_columns: exempt_columns.iter().map(|c| c.trim().to_lowercase()).collect(),
+        })
+    }
+
+    /// Heuristics that apply to the given column
+    pub fn for_column(&self, column: &str) -> Heuristics {
+        if self.exempt_columns.contains(&column.to_lowercase()) {
+            Heuristics::NONE
+        } else {
+            self.heuristics
+        }
+    }
+
+    pub fn heuristics(&self) -> Heuristics {
+        self.heuristics
+    }
+}
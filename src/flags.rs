@@ -0,0 +1,116 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Flag spellings used when `--bool-flags` or `--bool-flag COLUMN` gives none
+pub const DEFAULT_FLAG_PAIRS: &str = "Y/N,1/0,T/F";
+
+/// Accepted TRUE/FALSE spellings of a flag column, compared case-insensitively
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlagRule {
+    pairs: Vec<(String, String)>,
+}
+
+impl FlagRule {
+    /// Parse comma-separated `TRUE/FALSE` pairs, e.g. `Y/N,1/0`
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut pairs: Vec<(String, String)> = Vec::new();
+        for pair in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (yes, no) = pair
+                .split_once('/')
+                .with_context(|| format!("Invalid flag pair '{}' (expected TRUE/FALSE, e.g. Y/N)", pair))?;
+            let (yes, no) = (yes.trim().to_uppercase(), no.trim().to_uppercase());
+            if yes.is_empty() || no.is_empty() || yes == no {
+                anyhow::bail!("Invalid flag pair '{}' (expected two different values)", pair);
+            }
+            pairs.push((yes, no));
+        }
+
+        if pairs.is_empty() {
+            anyhow::bail!("No flag pairs in '{}'", spec);
+        }
+        for (yes, _) in &pairs {
+            if pairs.iter().any(|(_, no)| no == yes) {
+                anyhow::bail!("Flag value '{}' is both true and false in '{}'", yes, spec);
+            }
+        }
+        Ok(Self { pairs })
+    }
+
+    /// Boolean for a flag value; None when the value follows none of the pairs
+    pub fn convert(&self, value: &Value) -> Option<bool> {
+        let text = match value {
+            Value::Bool(b) => return Some(*b),
+            Value::String(s) => s.trim().to_uppercase(),
+            Value::Number(n) => n.to_string(),
+            _ => return None,
+        };
+        if self.pairs.iter().any(|(yes, _)| *yes == text) {
+            Some(true)
+        } else if self.pairs.iter().any(|(_, no)| *no == text) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+/// Which columns are converted from flags to Bool
+#[derive(Debug, Clone, Default)]
+pub struct BoolFlags {
+    /// Rule for any single-character text column whose values all follow it
+    global: Option<FlagRule>,
+    /// Columns converted whatever their values look like
+    columns: HashMap<String, FlagRule>,
+}
+
+impl BoolFlags {
+    /// Build the flag rules from CLI options
+    ///
+    /// # Arguments
+    /// * `global` - Pairs for `--bool-flags`, if given
+    /// * `columns` - `COLUMN` or `COLUMN=PAIRS` entries
+    pub fn from_args(global: Option<&str>, columns: &[String]) -> Result<Self> {
+        let global = global.map(FlagRule::parse).transpose()?;
+        let mut parsed = HashMap::new();
+        for entry in columns {
+            let (column, pairs) = entry.split_once('=').unwrap_or((entry, DEFAULT_FLAG_PAIRS));
+            let rule = FlagRule::parse(pairs).with_context(|| format!("Invalid --bool-flag '{}'", entry))?;
+            parsed.insert(column.trim().to_lowercase(), rule);
+        }
+
+        Ok(Self { global, columns: parsed })
+    }
+
+    /// Rule given for this column with `--bool-flag`
+    pub fn column_rule(&self, column: &str) -> Option<&FlagRule> {
+        self.columns.get(&column.to_lowercase())
+    }
+
+    /// Rule from `--bool-flags`, for columns that look like flags
+    pub fn global_rule(&self) -> Option<&FlagRule> {
+        self.global.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_flag_rules() {
+        let rule = FlagRule::parse(DEFAULT_FLAG_PAIRS).unwrap();
+        assert_eq!(rule.convert(&json!("y")), Some(true));
+        assert_eq!(rule.convert(&json!("N ")), Some(false));
+        assert_eq!(rule.convert(&json!(0)), Some(false));
+        assert_eq!(rule.convert(&json!("X")), None);
+        assert!(FlagRule::parse("Y/N,N/Y").is_err());
+        assert!(FlagRule::parse("YN").is_err());
+
+        let flags = BoolFlags::from_args(None, &["ACTIVE".to_string(), "OPEN=O/C".to_string()]).unwrap();
+        assert!(flags.global_rule().is_none());
+        assert_eq!(flags.column_rule("active"), Some(&rule));
+        assert_eq!(flags.column_rule("open").unwrap().convert(&json!("c")), Some(false));
+    }
+}
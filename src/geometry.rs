@@ -0,0 +1,323 @@
+use serde_json::Value;
+use std::sync::{Arc, LazyLock};
+use vortex_dtype::{DType, ExtDType, ExtID, ExtMetadata, Nullability};
+
+use crate::nested::Shape;
+use crate::object_types;
+
+/// GeoArrow extension type for geometries encoded as WKB
+///
+/// The metadata is GeoArrow's JSON: `{"crs": "<SRID>", "crs_type": "srid"}` when
+/// the column has a single SRID, `{}` otherwise.
+pub static WKB_ID: LazyLock<ExtID> = LazyLock::new(|| ExtID::from("geoarrow.wkb"));
+
+/// Whether an Oracle column type is SDO_GEOMETRY (with or without the MDSYS owner)
+pub fn is_geometry_type(oracle_type: &str) -> bool {
+    let name = oracle_type.trim().replace('"', "").to_uppercase();
+    name.rsplit('.').next() == Some("SDO_GEOMETRY")
+}
+
+/// Vortex dtype for a WKB geometry column
+pub fn wkb_dtype(srid: Option<i64>) -> DType {
+    let metadata = match srid {
+        Some(srid) => serde_json::json!({"crs": srid.to_string(), "crs_type": "srid"}),
+        None => serde_json::json!({}),
+    };
+    DType::Extension(Arc::new(ExtDType::new(
+        WKB_ID.clone(),
+        Arc::new(DType::Binary(Nullability::Nullable)),
+        Some(ExtMetadata::from(metadata.to_string().as_bytes())),
+    )))
+}
+
+/// Layout of SDO_GEOMETRY, used to read constructor text or structured values
+fn sdo_shape() -> Shape {
+    let field = |name: &str, shape| (name.to_string(), shape);
+    Shape::Struct(vec![
+        field("SDO_GTYPE", Shape::Int),
+        field("SDO_SRID", Shape::Int),
+        field(
+            "SDO_POINT",
+            Shape::Struct(vec![field("X", Shape::Float), field("Y", Shape::Float), field("Z", Shape::Float)]),
+        ),
+        field("SDO_ELEM_INFO", Shape::List(Box::new(Shape::Int))),
+        field("SDO_ORDINATES", Shape::List(Box::new(Shape::Float))),
+    ])
+}
+
+/// An Oracle SDO_GEOMETRY value
+#[derive(Debug, Clone, PartialEq)]
+pub struct SdoGeometry {
+    pub gtype: i64,
+    pub srid: Option<i64>,
+    point: Option<[Option<f64>; 3]>,
+    elem_info: Vec<i64>,
+    ordinates: Vec<f64>,
+}
+
+/// A geometry in the simple features model, one coordinate per vertex
+#[derive(Debug, Clone, PartialEq)]
+enum Geometry {
+    Point(Vec<f64>),
+    LineString(Vec<Vec<f64>>),
+    Polygon(Vec<Vec<Vec<f64>>>),
+    MultiPoint(Vec<Vec<f64>>),
+    MultiLineString(Vec<Vec<Vec<f64>>>),
+    MultiPolygon(Vec<Vec<Vec<Vec<f64>>>>),
+    Collection(Vec<Geometry>),
+}
+
+/// One element of SDO_ELEM_INFO with its coordinates
+enum Part {
+    Point(Vec<f64>),
+    Line(Vec<Vec<f64>>),
+    Ring { exterior: bool, coords: Vec<Vec<f64>> },
+}
+
+impl SdoGeometry {
+    /// Read a value as emitted by SQLcl: constructor text
+    /// (`MDSYS.SDO_GEOMETRY(2001, 4326, MDSYS.SDO_POINT_TYPE(2.35, 48.85, NULL), NULL, NULL)`)
+    /// or an object keyed by attribute name
+    pub fn from_value(value: &Value) -> Option<Self> {
+        let value = object_types::conform(&sdo_shape(), value);
+        let numbers = |key: &str| -> Option<Vec<Value>> {
+            match value.get(key)? {
+                Value::Array(items) => Some(items.clone()),
+                _ => None,
+            }
+        };
+
+        let point = value.get("SDO_POINT").filter(|p| p.is_object()).map(|p| {
+            ["X", "Y", "Z"].map(|axis| p.get(axis).and_then(Value::as_f64))
+        });
+        Some(Self {
+            gtype: value.get("SDO_GTYPE")?.as_i64()?,
+            srid: value.get("SDO_SRID").and_then(Value::as_i64),
+            point,
+            elem_info: numbers("SDO_ELEM_INFO").unwrap_or_default().iter().map(Value::as_i64).collect::<Option<_>>()?,
+            ordinates: numbers("SDO_ORDINATES").unwrap_or_default().iter().map(Value::as_f64).collect::<Option<_>>()?,
+        })
+    }
+
+    /// Dimension count and WKB type offset (Z: 1000, M: 2000, ZM: 3000)
+    fn dimensions(&self) -> Option<(usize, u32)> {
+        let dims = self.gtype / 1000;
+        let lrs = (self.gtype / 100) % 10;
+        match (dims, lrs) {
+            (2, 0) => Some((2, 0)),
+            (3, 0) => Some((3, 1000)),
+            (3, 3) => Some((3, 2000)),
+            (4, 0 | 4) => Some((4, 3000)),
+            _ => None,
+        }
+    }
+
+    /// Encode as ISO WKB (little-endian)
+    ///
+    /// Returns None for geometries WKB cannot hold as is: arcs, circles,
+    /// compound elements and unknown geometry types.
+    pub fn to_wkb(&self) -> Option<Vec<u8>> {
+        let (dims, type_offset) = self.dimensions()?;
+        let geometry = self.geometry(dims)?;
+        let mut out = Vec::new();
+        geometry.write_wkb(&mut out, type_offset);
+        Some(out)
+    }
+
+    fn geometry(&self, dims: usize) -> Option<Geometry> {
+        if self.elem_info.is_empty() {
+            // Point stored in SDO_POINT
+            let point = self.point?;
+            let coords = point[..dims.min(3)].iter().map(|c| c.unwrap_or(f64::NAN)).collect::<Vec<_>>();
+            return (self.gtype % 100 == 1 && coords.len() == dims).then_some(Geometry::Point(coords));
+        }
+
+        let parts = self.parts(dims)?;
+        let points = || -> Vec<Vec<f64>> {
+            parts.iter().filter_map(|p| match p {
+                Part::Point(c) => Some(c.clone()),
+                _ => None,
+            }).collect()
+        };
+        let lines = || -> Vec<Vec<Vec<f64>>> {
+            parts.iter().filter_map(|p| match p {
+                Part::Line(c) => Some(c.clone()),
+                _ => None,
+            }).collect()
+        };
+        let polygons = || -> Option<Vec<Vec<Vec<Vec<f64>>>>> {
+            let mut polygons: Vec<Vec<Vec<Vec<f64>>>> = Vec::new();
+            for part in &parts {
+                match part {
+                    Part::Ring { exterior: true, coords } => polygons.push(vec![coords.clone()]),
+                    Part::Ring { exterior: false, coords } => polygons.last_mut()?.push(coords.clone()),
+                    _ => {}
+                }
+            }
+            Some(polygons)
+        };
+
+        match self.gtype % 100 {
+            1 => points().into_iter().next().map(Geometry::Point),
+            2 => lines().into_iter().next().map(Geometry::LineString),
+            3 => polygons()?.into_iter().next().map(Geometry::Polygon),
+            5 => Some(Geometry::MultiPoint(points())),
+            6 => Some(Geometry::MultiLineString(lines())),
+            7 => Some(Geometry::MultiPolygon(polygons()?)),
+            4 => {
+                let mut members: Vec<Geometry> = points().into_iter().map(Geometry::Point).collect();
+                members.extend(lines().into_iter().map(Geometry::LineString));
+                members.extend(polygons()?.into_iter().map(Geometry::Polygon));
+                Some(Geometry::Collection(members))
+            }
+            _ => None,
+        }
+    }
+
+    /// Split SDO_ORDINATES along the SDO_ELEM_INFO triplets
+    fn parts(&self, dims: usize) -> Option<Vec<Part>> {
+        if !self.elem_info.len().is_multiple_of(3) {
+            return None;
+        }
+        let triplets: Vec<&[i64]> = self.elem_info.chunks(3).collect();
+        let mut parts = Vec::new();
+
+        for (i, triplet) in triplets.iter().enumerate() {
+            let start = usize::try_from(triplet[0] - 1).ok()?;
+            let end = match triplets.get(i + 1) {
+                Some(next) => usize::try_from(next[0] - 1).ok()?,
+                None => self.ordinates.len(),
+            };
+            let ordinates = self.ordinates.get(start..end)?;
+            if !ordinates.len().is_multiple_of(dims) {
+                return None;
+            }
+            let coords: Vec<Vec<f64>> = ordinates.chunks(dims).map(<[f64]>::to_vec).collect();
+
+            match (triplet[1], triplet[2]) {
+                (0, _) => {}
+                // Single point, or a cluster of n points
+                (1, n) if n >= 1 => parts.extend(coords.into_iter().map(Part::Point)),
+                (2, 1) => parts.push(Part::Line(coords)),
+                (1003 | 2003 | 3, 1) => parts.push(Part::Ring { exterior: triplet[1] != 2003, coords }),
+                // Rectangle given by its lower-left and upper-right corners
+                (1003 | 2003 | 3, 3) if dims == 2 && coords.len() == 2 => {
+                    let (min, max) = (&coords[0], &coords[1]);
+                    let ring = vec![
+                        vec![min[0], min[1]],
+                        vec![max[0], min[1]],
+                        vec![max[0], max[1]],
+                        vec![min[0], max[1]],
+                        vec![min[0], min[1]],
+                    ];
+                    parts.push(Part::Ring { exterior: triplet[1] != 2003, coords: ring });
+                }
+                // Arcs, circles and compound elements have no WKB equivalent
+                _ => return None,
+            }
+        }
+
+        Some(parts)
+    }
+}
+
+impl Geometry {
+    fn write_wkb(&self, out: &mut Vec<u8>, type_offset: u32) {
+        let base = match self {
+            Geometry::Point(_) => 1,
+            Geometry::LineString(_) => 2,
+            Geometry::Polygon(_) => 3,
+            Geometry::MultiPoint(_) => 4,
+            Geometry::MultiLineString(_) => 5,
+            Geometry::MultiPolygon(_) => 6,
+            Geometry::Collection(_) => 7,
+        };
+        out.push(1);
+        out.extend_from_slice(&(base + type_offset).to_le_bytes());
+
+        let count = |out: &mut Vec<u8>, n: usize| out.extend_from_slice(&(n as u32).to_le_bytes());
+        let coords = |out: &mut Vec<u8>, points: &[Vec<f64>]| {
+            count(out, points.len());
+            for c in points.iter().flatten() {
+                out.extend_from_slice(&c.to_le_bytes());
+            }
+        };
+
+        match self {
+            Geometry::Point(c) => c.iter().for_each(|c| out.extend_from_slice(&c.to_le_bytes())),
+            Geometry::LineString(points) => coords(out, points),
+            Geometry::Polygon(rings) => {
+                count(out, rings.len());
+                rings.iter().for_each(|ring| coords(out, ring));
+            }
+            Geometry::MultiPoint(points) => {
+                count(out, points.len());
+                points.iter().for_each(|p| Geometry::Point(p.clone()).write_wkb(out, type_offset));
+            }
+            Geometry::MultiLineString(lines) => {
+                count(out, lines.len());
+                lines.iter().for_each(|l| Geometry::LineString(l.clone()).write_wkb(out, type_offset));
+            }
+            Geometry::MultiPolygon(polygons) => {
+                count(out, polygons.len());
+                polygons.iter().for_each(|p| Geometry::Polygon(p.clone()).write_wkb(out, type_offset));
+            }
+            Geometry::Collection(members) => {
+                count(out, members.len());
+                members.iter().for_each(|g| g.write_wkb(out, type_offset));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn wkb(text: &str) -> Option<Vec<u8>> {
+        SdoGeometry::from_value(&json!(text))?.to_wkb()
+    }
+
+    #[test]
+    fn test_point_to_wkb() {
+        let geometry =
+            SdoGeometry::from_value(&json!("MDSYS.SDO_GEOMETRY(2001, 4326, MDSYS.SDO_POINT_TYPE(2.5, -1, NULL), NULL, NULL)"))
+                .unwrap();
+        assert_eq!(geometry.srid, Some(4326));
+
+        let mut expected = vec![1, 1, 0, 0, 0];
+        expected.extend_from_slice(&2.5f64.to_le_bytes());
+        expected.extend_from_slice(&(-1.0f64).to_le_bytes());
+        assert_eq!(geometry.to_wkb(), Some(expected));
+
+        // 3D point: WKB type 1001 (Point Z)
+        let z = wkb("SDO_GEOMETRY(3001, NULL, SDO_POINT_TYPE(1, 2, 3), NULL, NULL)").unwrap();
+        assert_eq!(&z[1..5], &1001u32.to_le_bytes());
+        assert_eq!(z.len(), 5 + 3 * 8);
+    }
+
+    #[test]
+    fn test_polygons_and_lines_to_wkb() {
+        // Rectangle expands to a closed 5-point ring
+        let rect = wkb("SDO_GEOMETRY(2003, NULL, NULL, SDO_ELEM_INFO_ARRAY(1, 1003, 3), SDO_ORDINATE_ARRAY(0, 0, 2, 1))").unwrap();
+        assert_eq!(&rect[1..5], &3u32.to_le_bytes());
+        assert_eq!(rect.len(), 5 + 4 + 4 + 5 * 16);
+
+        // Polygon with a hole, then a second polygon
+        let multi = wkb(
+            "SDO_GEOMETRY(2007, 8307, NULL, SDO_ELEM_INFO_ARRAY(1, 1003, 1, 9, 2003, 1, 17, 1003, 1), \
+             SDO_ORDINATE_ARRAY(0,0, 4,0, 4,4, 0,0, 1,1, 2,1, 2,2, 1,1, 5,5, 6,5, 6,6, 5,5))",
+        )
+        .unwrap();
+        assert_eq!(&multi[1..5], &6u32.to_le_bytes());
+        assert_eq!(&multi[5..9], &2u32.to_le_bytes());
+
+        let line = wkb("SDO_GEOMETRY(2002, NULL, NULL, SDO_ELEM_INFO_ARRAY(1, 2, 1), SDO_ORDINATE_ARRAY(0, 0, 1, 1))").unwrap();
+        assert_eq!(line.len(), 5 + 4 + 2 * 16);
+
+        // Arcs are not representable
+        assert_eq!(wkb("SDO_GEOMETRY(2002, NULL, NULL, SDO_ELEM_INFO_ARRAY(1, 2, 2), SDO_ORDINATE_ARRAY(0, 0, 1, 1, 2, 0))"), None);
+        assert!(is_geometry_type("MDSYS.SDO_GEOMETRY"));
+    }
+}
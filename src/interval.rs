@@ -0,0 +1,116 @@
+use std::sync::{Arc, LazyLock};
+use vortex_array::arrays::{PrimitiveArray, StructArray};
+use vortex_array::validity::Validity;
+use vortex_array::{ArrayRef, IntoArray};
+use vortex_buffer::Buffer;
+use vortex_dtype::{DType, ExtDType, ExtID, ExtMetadata, FieldNames, Nullability, PType, StructFields};
+
+/// Extension type for Oracle INTERVAL values
+///
+/// Storage is a struct `{months: i32, days: i32, nanos: i64}`, the layout of
+/// Arrow's `Interval(MonthDayNano)`. The metadata holds the Oracle interval kind.
+pub static INTERVAL_ID: LazyLock<ExtID> = LazyLock::new(|| ExtID::from("oracle2vortex.interval"));
+
+/// Oracle interval type, stored as the extension metadata
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntervalKind {
+    YearToMonth,
+    DayToSecond,
+}
+
+impl IntervalKind {
+    fn metadata(self) -> ExtMetadata {
+        let name: &[u8] = match self {
+            IntervalKind::YearToMonth => b"YEAR TO MONTH",
+            IntervalKind::DayToSecond => b"DAY TO SECOND",
+        };
+        ExtMetadata::from(name)
+    }
+}
+
+/// An interval split like Arrow's MonthDayNano: the fields are independent and
+/// share the sign of the Oracle value
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MonthDayNano {
+    pub months: i32,
+    pub days: i32,
+    pub nanos: i64,
+}
+
+impl MonthDayNano {
+    /// INTERVAL YEAR TO MONTH from a number of months
+    pub fn from_months(months: i32) -> Self {
+        Self { months, ..Default::default() }
+    }
+
+    /// INTERVAL DAY TO SECOND from a total number of microseconds
+    pub fn from_micros(micros: i64) -> Self {
+        const MICROS_PER_DAY: i64 = 86_400_000_000;
+        Self {
+            months: 0,
+            days: (micros / MICROS_PER_DAY) as i32,
+            nanos: (micros % MICROS_PER_DAY) * 1_000,
+        }
+    }
+}
+
+/// Storage dtype of the interval extension
+fn storage_dtype(nullability: Nullability) -> DType {
+    DType::Struct(
+        StructFields::new(
+            FieldNames::from(["months", "days", "nanos"]),
+            vec![
+                DType::Primitive(PType::I32, Nullability::NonNullable),
+                DType::Primitive(PType::I32, Nullability::NonNullable),
+                DType::Primitive(PType::I64, Nullability::NonNullable),
+            ],
+        ),
+        nullability,
+    )
+}
+
+/// Vortex dtype for an Oracle interval column
+pub fn interval_dtype(kind: IntervalKind, nullability: Nullability) -> DType {
+    DType::Extension(Arc::new(ExtDType::new(
+        INTERVAL_ID.clone(),
+        Arc::new(storage_dtype(nullability)),
+        Some(kind.metadata()),
+    )))
+}
+
+/// Storage array (nullable struct) for interval values
+pub fn interval_storage(values: &[Option<MonthDayNano>]) -> ArrayRef {
+    let value = |v: &Option<MonthDayNano>| v.unwrap_or_default();
+    let months: Buffer<i32> = values.iter().map(|v| value(v).months).collect();
+    let days: Buffer<i32> = values.iter().map(|v| value(v).days).collect();
+    let nanos: Buffer<i64> = values.iter().map(|v| value(v).nanos).collect();
+    let validity: Validity = values.iter().map(Option::is_some).collect();
+
+    StructArray::new(
+        FieldNames::from(["months", "days", "nanos"]),
+        vec![
+            PrimitiveArray::new(months, Validity::NonNullable).into_array(),
+            PrimitiveArray::new(days, Validity::NonNullable).into_array(),
+            PrimitiveArray::new(nanos, Validity::NonNullable).into_array(),
+        ],
+        values.len(),
+        validity,
+    )
+    .into_array()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_month_day_nano() {
+        // +01 02:00:00.000001
+        let mdn = MonthDayNano::from_micros(86_400_000_000 + 7_200_000_000 + 1);
+        assert_eq!(mdn, MonthDayNano { months: 0, days: 1, nanos: 7_200_000_001_000 });
+        // Negative intervals keep the sign on every field
+        let mdn = MonthDayNano::from_micros(-(86_400_000_000 + 1));
+        assert_eq!(mdn, MonthDayNano { months: 0, days: -1, nanos: -1_000 });
+        assert_eq!(MonthDayNano::from_months(-18).months, -18);
+    }
+}
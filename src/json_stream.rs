@@ -1,8 +1,25 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde_json::Value;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::ChildStdout;
 
+/// A column of the query result as described by SQLcl
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnInfo {
+    /// Column name as reported by Oracle (usually upper case)
+    pub name: String,
+    /// Oracle type name (e.g. `NUMBER`, `VARCHAR2`, `CLOB`)
+    pub oracle_type: String,
+}
+
+/// Records of a query together with the column metadata SQLcl emitted for them
+#[derive(Debug, Default)]
+pub struct QueryResult {
+    /// Columns in SELECT order (empty if SQLcl did not provide metadata)
+    pub columns: Vec<ColumnInfo>,
+    pub records: Vec<Value>,
+}
+
 pub struct JsonStreamReader {
     reader: BufReader<ChildStdout>,
     line_buffer: String,
@@ -16,7 +33,26 @@ impl JsonStreamReader {
         }
     }
 
-    pub async fn read_all_json(&mut self) -> Result<Vec<Value>> {
+    /// Extract the `columns` metadata of a SQLcl result set
+    fn parse_columns(result: &Value) -> Vec<ColumnInfo> {
+        result
+            .get("columns")
+            .and_then(|v| v.as_array())
+            .map(|columns| {
+                columns
+                    .iter()
+                    .filter_map(|c| {
+                        Some(ColumnInfo {
+                            name: c.get("name")?.as_str()?.to_string(),
+                            oracle_type: c.get("type").and_then(|t| t.as_str()).unwrap_or("").to_string(),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub async fn read_all_json(&mut self) -> Result<QueryResult> {
         // Read ALL stdout content first
         let mut full_output = String::new();
         
@@ -31,6 +67,11 @@ impl JsonStreamReader {
             full_output.push_str(&self.line_buffer);
         }
 
+        Self::parse_output(&full_output)
+    }
+
+    /// Parse SQLcl JSON output
+    pub fn parse_output(full_output: &str) -> Result<QueryResult> {
         tracing::info!("Parsing JSON content ({} bytes)", full_output.len());
 
         // Find the start of JSON - look for {"results" specifically
@@ -67,27 +108,48 @@ impl JsonStreamReader {
                 tracing::error!("Full output length: {} bytes", full_output.len());
             })?;
 
-        // Extract records from SQLcl's structure: {"results":[{"items":[...]}]}
-        let records = if let Some(results) = parsed.get("results").and_then(|v| v.as_array()) {
+        // Extract records from SQLcl's structure: {"results":[{"columns":[...],"items":[...]}]}
+        let (columns, records) = if let Some(results) = parsed.get("results").and_then(|v| v.as_array()) {
             if let Some(first_result) = results.first() {
+                let columns = Self::parse_columns(first_result);
                 if let Some(items) = first_result.get("items").and_then(|v| v.as_array()) {
-                    items.clone()
+                    (columns, items.clone())
                 } else {
-                    Vec::new()
+                    (columns, Vec::new())
                 }
             } else {
-                Vec::new()
+                (Vec::new(), Vec::new())
             }
         } else if let Some(array) = parsed.as_array() {
             // Fallback: if it's already an array
-            array.clone()
+            (Vec::new(), array.clone())
         } else {
             // Fallback: single object
-            vec![parsed]
+            (Vec::new(), vec![parsed])
         };
 
-        tracing::info!("Successfully parsed {} records from JSON", records.len());
+        tracing::info!("Successfully parsed {} records from JSON ({} columns described)", records.len(), columns.len());
+
+        Ok(QueryResult { columns, records })
+    }
+
+    /// Parse a `--table` spool file
+    ///
+    /// Spool files hold exactly SQLcl's JSON shape, so unlike SQLcl output
+    /// nothing is skipped: any other content is an error.
+    pub fn parse_spool(text: &str) -> Result<QueryResult> {
+        let parsed: Value = serde_json::from_str(text)?;
+        let result = parsed
+            .get("results")
+            .and_then(Value::as_array)
+            .and_then(|results| results.first())
+            .context("No results in spool file")?;
+        let records = result
+            .get("items")
+            .and_then(Value::as_array)
+            .context("No items in spool file")?
+            .clone();
 
-        Ok(records)
+        Ok(QueryResult { columns: Self::parse_columns(result), records })
     }
 }
@@ -0,0 +1,170 @@
+use anyhow::{Context, Result};
+use base64::Engine;
+use std::collections::HashMap;
+
+/// Oracle types handled as LOBs
+const LOB_TYPES: &[&str] = &["CLOB", "NCLOB", "BLOB", "BFILE"];
+
+/// Whether an Oracle type name (as reported by SQLcl) is a LOB type
+pub fn is_lob_type(oracle_type: &str) -> bool {
+    LOB_TYPES.iter().any(|t| oracle_type.eq_ignore_ascii_case(t))
+}
+
+/// Whether an Oracle type name is a binary LOB (values arrive encoded as text)
+pub fn is_binary_lob_type(oracle_type: &str) -> bool {
+    oracle_type.eq_ignore_ascii_case("BLOB") || oracle_type.eq_ignore_ascii_case("BFILE")
+}
+
+/// Suffix of the `DBMS_LOB.GETLENGTH` columns added by `--verify-lob-lengths`
+pub const LOB_LENGTH_SUFFIX: &str = "__LOB_LENGTH";
+
+/// Name of the LOB column a `<COL>__LOB_LENGTH` helper column measures, if `column` is one
+pub fn lob_length_target(column: &str) -> Option<&str> {
+    let split = column.len().checked_sub(LOB_LENGTH_SUFFIX.len())?;
+    if !column.is_char_boundary(split) || !column[split..].eq_ignore_ascii_case(LOB_LENGTH_SUFFIX) {
+        return None;
+    }
+    Some(&column[..split]).filter(|target| !target.is_empty())
+}
+
+/// Decode a base64 BLOB value (whitespace from line wrapping is ignored)
+pub fn decode_base64(s: &str) -> Option<Vec<u8>> {
+    let compact: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+    base64::engine::general_purpose::STANDARD.decode(compact).ok()
+}
+
+/// Encode bytes as base64, the inverse of `decode_base64`
+pub fn encode_base64(bytes: &[u8]) -> String {
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+/// What to do with the values of a LOB column
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LobMode {
+    /// Keep values in full
+    #[default]
+    Keep,
+    /// Drop the column from the output
+    Skip,
+    /// Keep at most this many bytes of each value
+    Truncate(usize),
+    /// When a value is larger than this many bytes, write every value to
+    /// `<output>.lobs/<sha256>` and keep a reference in the column
+    External(usize),
+}
+
+impl LobMode {
+    /// Parse `keep`, `skip`, `truncate:N` or `external:N`
+    ///
+    /// `external` needs an explicit threshold; `external:0` externalises any
+    /// column holding a non-empty value.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim().to_lowercase();
+        let bytes = |count: &str| -> Result<usize> {
+            count
+                .trim()
+                .parse()
+                .with_context(|| format!("Invalid byte count in LOB mode '{}'", spec))
+        };
+
+        match spec.as_str() {
+            "keep" => Ok(LobMode::Keep),
+            "skip" => Ok(LobMode::Skip),
+            "external" => anyhow::bail!(
+                "LOB mode 'external' needs a threshold: external:N (external:0 for every value)"
+            ),
+            _ => {
+                if let Some(count) = spec.strip_prefix("truncate:") {
+                    Ok(LobMode::Truncate(bytes(count)?))
+                } else if let Some(count) = spec.strip_prefix("external:") {
+                    Ok(LobMode::External(bytes(count)?))
+                } else {
+                    anyhow::bail!(
+                        "Unknown LOB mode '{}' (expected keep, skip, truncate:N or external:N)",
+                        spec
+                    )
+                }
+            }
+        }
+    }
+}
+
+/// LOB handling for all columns, with per-column overrides
+#[derive(Debug, Clone, Default)]
+pub struct LobPolicy {
+    default: LobMode,
+    columns: HashMap<String, LobMode>,
+}
+
+impl LobPolicy {
+    /// Build a policy from CLI options
+    ///
+    /// # Arguments
+    /// * `default` - Mode for LOB columns without an override
+    /// * `columns` - `COLUMN=mode` overrides
+    pub fn from_args(default: LobMode, columns: &[String]) -> Result<Self> {
+        let mut parsed = HashMap::new();
+        for entry in columns {
+            let (column, mode) = entry
+                .split_once('=')
+                .with_context(|| format!("Invalid --lob '{}' (expected COLUMN=mode)", entry))?;
+            parsed.insert(column.trim().to_lowercase(), LobMode::parse(mode)?);
+        }
+
+        Ok(Self { default, columns: parsed })
+    }
+
+    /// Mode that applies to a LOB column
+    pub fn mode_for(&self, column: &str) -> LobMode {
+        self.columns.get(&column.to_lowercase()).copied().unwrap_or(self.default)
+    }
+}
+
+/// Cut a string to at most `max_bytes` bytes without splitting a character
+pub fn truncate_text(s: &str, max_bytes: usize) -> &str {
+    if s.len() <= max_bytes {
+        return s;
+    }
+    let mut end = max_bytes;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lob_modes() {
+        assert_eq!(LobMode::parse("skip").unwrap(), LobMode::Skip);
+        assert_eq!(LobMode::parse("Truncate:4096").unwrap(), LobMode::Truncate(4096));
+        assert!(LobMode::parse("truncate:abc").is_err());
+        assert!(LobMode::parse("external").is_err());
+        assert_eq!(LobMode::parse("external:0").unwrap(), LobMode::External(0));
+        assert_eq!(LobMode::parse("external:65536").unwrap(), LobMode::External(65536));
+
+        let policy = LobPolicy::from_args(LobMode::Skip, &["NOTES=keep".to_string()]).unwrap();
+        assert_eq!(policy.mode_for("notes"), LobMode::Keep);
+        assert_eq!(policy.mode_for("document"), LobMode::Skip);
+    }
+
+    #[test]
+    fn test_truncate_text() {
+        assert_eq!(truncate_text("hello", 10), "hello");
+        assert_eq!(truncate_text("hello", 3), "hel");
+        assert_eq!(truncate_text("héllo", 2), "h"); // é is 2 bytes
+    }
+
+    #[test]
+    fn test_lob_length_helpers() {
+        assert_eq!(lob_length_target("DOC__LOB_LENGTH"), Some("DOC"));
+        assert_eq!(lob_length_target("doc__lob_length"), Some("doc"));
+        assert_eq!(lob_length_target("__LOB_LENGTH"), None);
+        assert_eq!(lob_length_target("DOC"), None);
+
+        assert_eq!(decode_base64("SGVs\nbG8="), Some(b"Hello".to_vec()));
+        assert_eq!(decode_base64("not base64!"), None);
+    }
+}
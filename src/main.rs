@@ -1,15 +1,39 @@
 mod cli;
+mod columns;
 mod csv_stream;  // Keep for future CSV mode option
+mod detection;
+mod flags;
+mod geometry;
+mod interval;
 mod json_stream;
+mod lobs;
+mod nested;
+mod object_types;
+mod partitions;
 mod pipeline;
+mod raw;
+mod rejects;
+mod schema;
+mod session;
+mod snapshot;
 mod sqlcl;
+mod vector;
 mod vortex_writer;
 
 use anyhow::Result;
 use clap::Parser;
 use cli::CliArgs;
+use columns::ColumnLayout;
+use detection::DetectionPolicy;
+use flags::BoolFlags;
+use lobs::{LobMode, LobPolicy};
+use partitions::{TableExport, TableRef};
 use pipeline::Pipeline;
-use sqlcl::SqlclConfig;
+use schema::SchemaOverrides;
+use session::SessionInit;
+use snapshot::Snapshot;
+use sqlcl::{LobFetchSettings, SqlclConfig};
+use vortex_writer::WriterConfig;
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -27,7 +51,11 @@ async fn main() -> Result<()> {
     args.validate()?;
 
     tracing::info!("Starting oracle2vortex");
-    tracing::info!("SQL file: {:?}", args.sql_file);
+    match (&args.sql_file, &args.table) {
+        (Some(sql_file), _) => tracing::info!("SQL file: {:?}", sql_file),
+        (None, Some(table)) => tracing::info!("Table: {} ({:?} output)", table, args.partition_output),
+        (None, None) => {}
+    }
     tracing::info!("Output file: {:?}", args.output);
     
     // Build connection string from args
@@ -52,26 +80,96 @@ async fn main() -> Result<()> {
         }
     }
     
-    if args.skip_lobs {
-        tracing::info!("LOB filtering: ENABLED (CLOB, BLOB, NCLOB columns will be skipped)");
+    let default_lob_mode = if args.skip_lobs { LobMode::Skip } else { args.lob_mode };
+    let lobs = LobPolicy::from_args(default_lob_mode, &args.lob_columns)?;
+    if default_lob_mode != LobMode::Keep {
+        tracing::info!("LOB handling: {:?} (CLOB, BLOB, NCLOB, BFILE columns)", default_lob_mode);
     }
     
+    let schema = SchemaOverrides::load(args.schema_file.as_deref(), &args.casts)?;
+    if !schema.is_empty() {
+        tracing::info!("Column type overrides: {} columns", schema.len());
+    }
+
+    let detection = DetectionPolicy::from_args(
+        &args.detect,
+        args.no_detect_binary,
+        args.strings_only,
+        &args.no_detect_columns,
+    )?;
+    tracing::info!("Type detection: {:?}", detection.heuristics());
+
+    let bool_flags = BoolFlags::from_args(args.bool_flags.as_deref(), &args.bool_flag_columns)?;
+
+    let layout = ColumnLayout::from_args(
+        &args.column_order,
+        &args.renames,
+        &args.include,
+        &args.exclude,
+        args.rename_case,
+    )?;
+
     if args.thick {
         tracing::info!("Oracle driver: THICK (JDBC/OCI mode)");
     } else {
         tracing::info!("Oracle driver: THIN (default mode)");
     }
 
+    let session_init = match &args.session_init {
+        Some(path) => SessionInit::from_file(path)?,
+        None => SessionInit::default(),
+    };
+
     // Create SQLcl configuration
     let config = SqlclConfig {
         connection_string,
         sqlcl_path: args.sqlcl_path.to_string_lossy().to_string(),
         thick: args.thick,
+        lob_fetch: LobFetchSettings {
+            long: args.long,
+            long_chunk_size: args.long_chunk_size,
+            lob_prefetch: args.lob_prefetch,
+        },
+        session_init,
+        as_of_scn: None,
+    };
+
+    let writer_config = WriterConfig {
+        lobs,
+        schema,
+        detection,
+        layout,
+        schema_drift: args.on_schema_drift,
+        // --strict must see LOBs cut by SQLcl, which only the length columns reveal
+        verify_lob_lengths: args.verify_lob_lengths || args.strict,
+        time_zone_columns: args.time_zone_columns,
+        oracle_date_as: args.oracle_date_as,
+        nested_json: args.nested_json,
+        bool_flags,
+        wide_numbers: args.wide_numbers,
+        narrow_by_values: args.narrow_by_values,
+        raw16_as: args.raw16_as,
+        rejects_path: args.rejects.clone(),
+        max_rejects: args.max_rejects,
+        strict: args.strict,
     };
 
     // Create and run pipeline
-    let pipeline = Pipeline::new(config, args.batch_size, args.auto_batch_rows, args.skip_lobs);
-    pipeline.run(&args.sql_file, &args.output).await?;
+    let snapshot = Snapshot::from_args(args.as_of_scn, args.as_of_timestamp.as_deref(), args.no_snapshot)?;
+    let mut pipeline = Pipeline::new(config, args.batch_size, args.auto_batch_rows, writer_config, snapshot);
+    match (&args.sql_file, &args.table) {
+        (Some(sql_file), _) => pipeline.run(sql_file, &args.output).await?,
+        (None, Some(table)) => {
+            let export = TableExport {
+                table: TableRef::parse(table)?,
+                output: args.partition_output,
+                parallel: args.parallel,
+                resume: args.resume,
+            };
+            pipeline.run_table(&export, &args.output).await?;
+        }
+        (None, None) => anyhow::bail!("Either --sql-file or --table must be given"),
+    }
 
     tracing::info!("Successfully completed");
 
@@ -0,0 +1,212 @@
+use serde_json::Value;
+use vortex_array::arrays::{BoolArray, ListArray, PrimitiveArray, StructArray, VarBinArray};
+use vortex_array::validity::Validity;
+use vortex_array::{ArrayRef, IntoArray};
+use vortex_buffer::Buffer;
+use vortex_dtype::{DType, FieldNames, Nullability};
+
+/// Shape of a JSON value, widened across all the values of a column
+///
+/// Objects become structs and arrays become lists. Integers widen to floats and
+/// mixed scalars widen to strings. Values that cannot share a shape (an object and
+/// a number, say) fall back to `Text`: a Utf8 column holding the JSON text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    /// Only nulls (or empty arrays) seen so far
+    Null,
+    Bool,
+    Int,
+    Float,
+    String,
+    List(Box<Shape>),
+    /// Fields in order of first appearance
+    Struct(Vec<(String, Shape)>),
+    /// Heterogeneous values, stored as JSON text
+    Text,
+}
+
+impl Shape {
+    /// Shape of a single value
+    pub fn of(value: &Value) -> Self {
+        match value {
+            Value::Null => Shape::Null,
+            Value::Bool(_) => Shape::Bool,
+            Value::Number(n) if n.is_i64() => Shape::Int,
+            Value::Number(_) => Shape::Float,
+            Value::String(_) => Shape::String,
+            Value::Array(items) => Shape::List(Box::new(
+                items.iter().fold(Shape::Null, |shape, item| shape.merge(Shape::of(item))),
+            )),
+            Value::Object(map) => Shape::Struct(map.iter().map(|(k, v)| (k.clone(), Shape::of(v))).collect()),
+        }
+    }
+
+    /// Widen the shape of every value of a column
+    pub fn infer<'a>(values: impl IntoIterator<Item = &'a Value>) -> Self {
+        values.into_iter().fold(Shape::Null, |shape, value| shape.merge(Shape::of(value)))
+    }
+
+    /// Smallest shape holding both `self` and `other`
+    pub fn merge(self, other: Shape) -> Shape {
+        match (self, other) {
+            (Shape::Null, other) | (other, Shape::Null) => other,
+            (Shape::Text, _) | (_, Shape::Text) => Shape::Text,
+            (a, b) if a == b => a,
+            (Shape::Int, Shape::Float) | (Shape::Float, Shape::Int) => Shape::Float,
+            (a, b) if a.is_scalar() && b.is_scalar() => Shape::String,
+            (Shape::List(a), Shape::List(b)) => Shape::List(Box::new(a.merge(*b))),
+            (Shape::Struct(mut fields), Shape::Struct(other)) => {
+                for (name, shape) in other {
+                    match fields.iter_mut().find(|(n, _)| *n == name) {
+                        Some((_, existing)) => *existing = std::mem::replace(existing, Shape::Null).merge(shape),
+                        None => fields.push((name, shape)),
+                    }
+                }
+                Shape::Struct(fields)
+            }
+            _ => Shape::Text,
+        }
+    }
+
+    fn is_scalar(&self) -> bool {
+        matches!(self, Shape::Bool | Shape::Int | Shape::Float | Shape::String)
+    }
+
+    /// Paths (`field.sub[]`) that fell back to JSON text, for logging
+    pub fn text_paths(&self) -> Vec<String> {
+        fn walk(shape: &Shape, path: String, out: &mut Vec<String>) {
+            match shape {
+                Shape::Text => out.push(path),
+                Shape::List(element) => walk(element, format!("{}[]", path), out),
+                Shape::Struct(fields) => {
+                    for (name, field) in fields {
+                        let sub = if path.is_empty() { name.clone() } else { format!("{}.{}", path, name) };
+                        walk(field, sub, out);
+                    }
+                }
+                _ => {}
+            }
+        }
+        let mut out = Vec::new();
+        walk(self, String::new(), &mut out);
+        out
+    }
+}
+
+/// Build a nullable array of `shape` from one value per row (None or JSON null is null)
+pub fn build_array(shape: &Shape, values: &[Option<&Value>]) -> ArrayRef {
+    let values: Vec<Option<&Value>> = values.iter().map(|v| v.filter(|v| !v.is_null())).collect();
+    let validity = |valid: &dyn Fn(&Value) -> bool| -> Validity {
+        values.iter().map(|v| v.map(valid).unwrap_or(false)).collect()
+    };
+
+    match shape {
+        Shape::Null | Shape::String | Shape::Text => {
+            let text = values.iter().map(|v| {
+                v.map(|v| match v {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+            });
+            VarBinArray::from_iter(text, DType::Utf8(Nullability::Nullable)).into_array()
+        }
+        Shape::Bool => {
+            let bits: vortex_buffer::BitBuffer =
+                values.iter().map(|v| v.and_then(Value::as_bool).unwrap_or(false)).collect();
+            BoolArray::new(bits, validity(&|v| v.is_boolean())).into_array()
+        }
+        Shape::Int => {
+            let buffer: Buffer<i64> = values.iter().map(|v| v.and_then(Value::as_i64).unwrap_or(0)).collect();
+            PrimitiveArray::new(buffer, validity(&|v| v.is_i64())).into_array()
+        }
+        Shape::Float => {
+            let buffer: Buffer<f64> = values.iter().map(|v| v.and_then(Value::as_f64).unwrap_or(0.0)).collect();
+            PrimitiveArray::new(buffer, validity(&|v| v.is_number())).into_array()
+        }
+        Shape::List(element) => {
+            let mut offsets = Vec::with_capacity(values.len() + 1);
+            let mut items = Vec::new();
+            offsets.push(0u64);
+            for value in &values {
+                if let Some(Value::Array(list)) = value {
+                    items.extend(list.iter().map(Some));
+                }
+                offsets.push(items.len() as u64);
+            }
+            ListArray::new(
+                build_array(element, &items),
+                PrimitiveArray::new(Buffer::from(offsets), Validity::NonNullable).into_array(),
+                validity(&|v| v.is_array()),
+            )
+            .into_array()
+        }
+        Shape::Struct(fields) => {
+            let names: Vec<&str> = fields.iter().map(|(name, _)| name.as_str()).collect();
+            let children: Vec<ArrayRef> = fields
+                .iter()
+                .map(|(name, field)| {
+                    let child: Vec<Option<&Value>> =
+                        values.iter().map(|v| v.and_then(|v| v.as_object()?.get(name))).collect();
+                    build_array(field, &child)
+                })
+                .collect();
+            StructArray::new(FieldNames::from(names), children, values.len(), validity(&|v| v.is_object()))
+                .into_array()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use vortex_array::ToCanonical;
+
+    #[test]
+    fn test_infer_widens_shapes() {
+        let docs = [
+            json!({"id": 1, "tags": ["a"], "price": 10}),
+            json!({"id": 2, "tags": [], "price": 9.5, "extra": {"x": true}}),
+            json!(null),
+        ];
+        assert_eq!(
+            Shape::infer(&docs),
+            Shape::Struct(vec![
+                ("id".to_string(), Shape::Int),
+                ("price".to_string(), Shape::Float),
+                ("tags".to_string(), Shape::List(Box::new(Shape::String))),
+                ("extra".to_string(), Shape::Struct(vec![("x".to_string(), Shape::Bool)])),
+            ])
+        );
+
+        // Mixed scalars widen to strings, objects mixed with scalars fall back to text
+        assert_eq!(Shape::infer(&[json!(1), json!("a")]), Shape::String);
+        let mixed = Shape::infer(&[json!({"v": {"a": 1}}), json!({"v": 3})]);
+        assert_eq!(mixed.text_paths(), vec!["v".to_string()]);
+    }
+
+    #[test]
+    fn test_build_nested_array() {
+        let docs = [json!({"id": 1, "tags": ["a", "b"]}), json!(null), json!({"id": 2, "tags": null})];
+        let values: Vec<Option<&Value>> = docs.iter().map(Some).collect();
+        let shape = Shape::infer(&docs);
+        let array = build_array(&shape, &values);
+        assert_eq!(array.len(), 3);
+        assert!(matches!(array.dtype(), DType::Struct(_, Nullability::Nullable)));
+
+        // Null documents are null struct rows, a null `tags` is a null list
+        let docs = array.to_struct();
+        assert_eq!((0..3).map(|i| docs.is_valid(i)).collect::<Vec<_>>(), vec![true, false, true]);
+        let ids = docs.field_by_name("id").unwrap().to_primitive();
+        assert_eq!(ids.as_slice::<i64>()[0], 1);
+        assert_eq!(ids.as_slice::<i64>()[2], 2);
+        let tags = docs.field_by_name("tags").unwrap().to_listview();
+        assert_eq!((0..3).map(|i| tags.is_valid(i)).collect::<Vec<_>>(), vec![true, false, false]);
+        let first = tags.list_elements_at(0);
+        assert_eq!(
+            (0..first.len()).map(|i| first.scalar_at(i).as_utf8().value().unwrap().to_string()).collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+        assert_eq!(tags.list_elements_at(2).len(), 0);
+    }
+}
@@ -0,0 +1,428 @@
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+
+use crate::nested::Shape;
+
+/// Types that need no catalog lookup, by first word of the type name: built-in
+/// Oracle types, and SDO_GEOMETRY, which is decoded to WKB
+const BUILTIN_TYPES: &[&str] = &[
+    "VARCHAR2", "NVARCHAR2", "VARCHAR", "CHAR", "NCHAR", "NUMBER", "FLOAT", "INTEGER", "INT", "SMALLINT",
+    "DECIMAL", "REAL", "DOUBLE", "BINARY_FLOAT", "BINARY_DOUBLE", "DATE", "TIMESTAMP", "INTERVAL", "CLOB",
+    "NCLOB", "BLOB", "BFILE", "RAW", "LONG", "ROWID", "UROWID", "JSON", "BOOLEAN", "VECTOR", "XMLTYPE",
+    "SDO_GEOMETRY",
+];
+
+/// Nesting limit when resolving type definitions (guards against recursive types)
+const MAX_DEPTH: usize = 16;
+
+/// Whether an Oracle column type is built in, rather than a user-defined object
+/// type, VARRAY or nested table
+pub fn is_builtin_type(oracle_type: &str) -> bool {
+    let name = base_name(oracle_type);
+    let first = name.split(|c: char| c == '(' || c.is_whitespace()).next().unwrap_or("");
+    first.is_empty() || BUILTIN_TYPES.contains(&first)
+}
+
+/// Upper-case type name without owner or quotes (`"HR"."ADDRESS_T"` → `ADDRESS_T`)
+fn base_name(type_name: &str) -> String {
+    let name = type_name.trim().replace('"', "").to_uppercase();
+    match name.split_once('(') {
+        // Keep arguments such as NUMBER(10,2) intact, strip the owner before them
+        Some((head, args)) => format!("{}({}", head.rsplit('.').next().unwrap_or(head), args),
+        None => name.rsplit('.').next().unwrap_or(&name).to_string(),
+    }
+}
+
+/// Owner and name of a user-defined type (`"HR"."ADDRESS_T"` → `HR`, `ADDRESS_T`)
+///
+/// A name without owner refers to a type of the current schema.
+fn split_owner(type_name: &str) -> (Option<String>, String) {
+    let name = type_name.trim().replace('"', "").to_uppercase();
+    match name.rsplit_once('.') {
+        Some((owner, name)) => (Some(owner.to_string()), name.to_string()),
+        None => (None, name),
+    }
+}
+
+/// Type of an object attribute or collection element
+#[derive(Debug, Clone, PartialEq)]
+struct TypeRef {
+    /// None for built-in types
+    owner: Option<String>,
+    name: String,
+    precision: Option<u64>,
+    scale: Option<i64>,
+}
+
+/// Definition of a user-defined type
+#[derive(Debug, Clone, PartialEq)]
+enum UserType {
+    /// Object type: attributes in declaration order
+    Object(Vec<(String, TypeRef)>),
+    /// VARRAY or nested table
+    Collection(TypeRef),
+}
+
+/// User-defined type definitions read from ALL_TYPE_ATTRS and ALL_COLL_TYPES,
+/// keyed by owner and name
+#[derive(Debug, Clone, Default)]
+pub struct TypeCatalog {
+    types: HashMap<(String, String), UserType>,
+    /// Owner of the types named without one, as reported with the definitions
+    current_schema: Option<String>,
+}
+
+impl TypeCatalog {
+    /// Query returning the definitions of `type_names` (`OWNER.NAME`, or `NAME`
+    /// for a type of the current schema), one row per object attribute or collection
+    pub fn definition_query(type_names: &[String]) -> String {
+        let filter = type_names
+            .iter()
+            .map(|type_name| {
+                let (owner, name) = split_owner(type_name);
+                let owner = match owner {
+                    Some(owner) => format!("'{}'", owner.replace('\'', "''")),
+                    None => "SYS_CONTEXT('USERENV', 'CURRENT_SCHEMA')".to_string(),
+                };
+                format!("(OWNER = {} AND TYPE_NAME = '{}')", owner, name.replace('\'', "''"))
+            })
+            .collect::<Vec<_>>()
+            .join(" OR ");
+        format!(
+            "SELECT OWNER AS TYPE_OWNER, TYPE_NAME, 'OBJECT' AS KIND, ATTR_NO AS POS, ATTR_NAME, \
+             ATTR_TYPE_OWNER AS ELEM_OWNER, ATTR_TYPE_NAME AS ELEM_TYPE, PRECISION, SCALE, \
+             SYS_CONTEXT('USERENV', 'CURRENT_SCHEMA') AS CURRENT_SCHEMA \
+             FROM ALL_TYPE_ATTRS WHERE {filter} \
+             UNION ALL \
+             SELECT OWNER, TYPE_NAME, 'COLLECTION', 1, NULL, ELEM_TYPE_OWNER, ELEM_TYPE_NAME, PRECISION, SCALE, \
+             SYS_CONTEXT('USERENV', 'CURRENT_SCHEMA') \
+             FROM ALL_COLL_TYPES WHERE {filter} \
+             ORDER BY 1, 2, 4"
+        )
+    }
+
+    /// Add the rows returned by `definition_query`
+    pub fn add_rows(&mut self, rows: &[Value]) {
+        for row in rows {
+            let text = |key: &str| row.get(key).and_then(Value::as_str).map(|s| s.trim().to_uppercase());
+            if let Some(schema) = text("current_schema") {
+                self.current_schema = Some(schema);
+            }
+            let (Some(owner), Some(type_name), Some(kind), Some(elem_type)) =
+                (text("type_owner"), text("type_name"), text("kind"), text("elem_type"))
+            else {
+                continue;
+            };
+            let type_ref = TypeRef {
+                owner: text("elem_owner"),
+                name: base_name(&elem_type),
+                precision: row.get("precision").and_then(Value::as_u64),
+                scale: row.get("scale").and_then(Value::as_i64),
+            };
+
+            let key = (owner, type_name);
+            if kind == "COLLECTION" {
+                self.types.insert(key, UserType::Collection(type_ref));
+            } else if let Some(attr) = text("attr_name") {
+                match self.types.entry(key).or_insert_with(|| UserType::Object(Vec::new())) {
+                    UserType::Object(attrs) => attrs.push((attr, type_ref)),
+                    UserType::Collection(_) => {}
+                }
+            }
+        }
+    }
+
+    /// Definition of a type, looking up names without owner in the current schema
+    fn get(&self, owner: Option<&str>, name: &str) -> Option<&UserType> {
+        let owner = owner.or(self.current_schema.as_deref())?;
+        self.types.get(&(owner.to_string(), name.to_string()))
+    }
+
+    /// User-defined types among `type_names` and the attributes and elements of
+    /// known types that are not in the catalog yet, as `OWNER.NAME` (or `NAME`
+    /// while the current schema is unknown)
+    pub fn missing_types<'a>(&self, type_names: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+        let named = type_names
+            .into_iter()
+            .filter(|name| !is_builtin_type(name))
+            .map(split_owner);
+        let referenced = self.types.values().flat_map(|t| match t {
+            UserType::Object(attrs) => attrs.iter().map(|(_, r)| r).collect::<Vec<_>>(),
+            UserType::Collection(element) => vec![element],
+        });
+        let referenced = referenced
+            .filter(|r| !is_builtin_type(&r.name))
+            .map(|r| (r.owner.clone(), r.name.clone()));
+
+        let mut missing: Vec<String> = named
+            .chain(referenced)
+            .filter(|(owner, name)| self.get(owner.as_deref(), name).is_none())
+            .map(|(owner, name)| match owner.or_else(|| self.current_schema.clone()) {
+                Some(owner) => format!("{}.{}", owner, name),
+                None => name,
+            })
+            .collect();
+        missing.sort();
+        missing.dedup();
+        missing
+    }
+
+    /// Nested shape mirroring the definition of a user-defined type
+    pub fn shape(&self, type_name: &str) -> Option<Shape> {
+        let (owner, name) = split_owner(type_name);
+        self.get(owner.as_deref(), &name)?;
+        Some(self.shape_of(&TypeRef { owner, name, precision: None, scale: None }, 0))
+    }
+
+    fn shape_of(&self, type_ref: &TypeRef, depth: usize) -> Shape {
+        if depth > MAX_DEPTH {
+            return Shape::Text;
+        }
+        let name = base_name(&type_ref.name);
+        match self.get(type_ref.owner.as_deref(), &name) {
+            Some(UserType::Object(attrs)) => Shape::Struct(
+                attrs.iter().map(|(attr, r)| (attr.clone(), self.shape_of(r, depth + 1))).collect(),
+            ),
+            Some(UserType::Collection(element)) => Shape::List(Box::new(self.shape_of(element, depth + 1))),
+            None => match name.as_str() {
+                "INTEGER" | "INT" | "SMALLINT" => Shape::Int,
+                "NUMBER" if type_ref.scale == Some(0) && type_ref.precision.is_some_and(|p| p <= 18) => Shape::Int,
+                // Up to 38 digits, beyond what I64 or F64 hold: kept as text
+                "NUMBER" if type_ref.precision.is_none() || type_ref.scale == Some(0) => Shape::String,
+                "NUMBER" | "FLOAT" | "REAL" | "BINARY_FLOAT" | "BINARY_DOUBLE" => Shape::Float,
+                "BOOLEAN" => Shape::Bool,
+                _ if is_builtin_type(&name) => Shape::String,
+                // Type not found in the catalog
+                _ => Shape::Text,
+            },
+        }
+    }
+}
+
+/// Align a value with `shape`
+///
+/// Object values may be constructor text (`ADDRESS_T('1 Main St', 'Paris')`),
+/// positional arrays or objects keyed by attribute name (case-insensitive).
+pub fn conform(shape: &Shape, value: &Value) -> Value {
+    let value = match value {
+        Value::String(s) if matches!(shape, Shape::Struct(_) | Shape::List(_)) => {
+            parse_constructor(s).unwrap_or_else(|| value.clone())
+        }
+        _ => value.clone(),
+    };
+
+    match (shape, value) {
+        (_, Value::Null) => Value::Null,
+        (Shape::Struct(attrs), Value::Array(args)) => Value::Object(
+            attrs
+                .iter()
+                .zip(args.iter().map(Some).chain(std::iter::repeat(None)))
+                .map(|((name, attr), arg)| (name.clone(), arg.map(|a| conform(attr, a)).unwrap_or(Value::Null)))
+                .collect(),
+        ),
+        (Shape::Struct(attrs), Value::Object(map)) => Value::Object(
+            attrs
+                .iter()
+                .map(|(name, attr)| {
+                    let value = map.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)).map(|(_, v)| v);
+                    (name.clone(), value.map(|v| conform(attr, v)).unwrap_or(Value::Null))
+                })
+                .collect::<Map<_, _>>(),
+        ),
+        (Shape::List(element), Value::Array(items)) => {
+            Value::Array(items.iter().map(|item| conform(element, item)).collect())
+        }
+        (Shape::Int | Shape::Float, Value::String(s)) => parse_number(&s).unwrap_or(Value::Null),
+        (Shape::Int, Value::Number(n)) if !n.is_i64() => {
+            n.as_f64().filter(|f| f.fract() == 0.0).map(|f| Value::from(f as i64)).unwrap_or(Value::Null)
+        }
+        (Shape::Struct(_) | Shape::List(_), _) => Value::Null,
+        (_, value) => value,
+    }
+}
+
+/// Parse Oracle constructor text into nested arrays of arguments
+///
+/// `ADDRESS_T('1 Main St', NULL, PHONES_T('555', '556'))` gives
+/// `["1 Main St", null, ["555", "556"]]`. Constructor names are dropped.
+pub fn parse_constructor(text: &str) -> Option<Value> {
+    let mut parser = ConstructorParser { chars: text.trim().chars().collect(), pos: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    (parser.pos == parser.chars.len() && value.is_array()).then_some(value)
+}
+
+struct ConstructorParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl ConstructorParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn value(&mut self) -> Option<Value> {
+        self.skip_whitespace();
+        match self.peek()? {
+            '\'' => self.string().map(Value::String),
+            c if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' => {
+                let start = self.pos;
+                while self.peek().is_some_and(|c| c.is_ascii_alphanumeric() || "+-.".contains(c)) {
+                    self.pos += 1;
+                }
+                // Numbers beyond I64 keep their text, so that string attributes get every digit
+                let text: String = self.chars[start..self.pos].iter().collect();
+                match text.parse::<i64>() {
+                    Ok(i) => Some(Value::from(i)),
+                    Err(_) => parse_number(&text).map(|_| Value::String(text)),
+                }
+            }
+            _ => {
+                let start = self.pos;
+                while self.peek().is_some_and(|c| c.is_alphanumeric() || "_$#.\"".contains(c)) {
+                    self.pos += 1;
+                }
+                let name: String = self.chars[start..self.pos].iter().collect();
+                self.skip_whitespace();
+                if self.peek() == Some('(') {
+                    self.pos += 1;
+                    self.arguments().map(Value::Array)
+                } else if name.eq_ignore_ascii_case("NULL") {
+                    Some(Value::Null)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Arguments after the opening parenthesis, up to the closing one
+    fn arguments(&mut self) -> Option<Vec<Value>> {
+        let mut args = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(')') {
+            self.pos += 1;
+            return Some(args);
+        }
+        loop {
+            args.push(self.value()?);
+            self.skip_whitespace();
+            match self.peek()? {
+                ',' => self.pos += 1,
+                ')' => {
+                    self.pos += 1;
+                    return Some(args);
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    /// Quoted literal, with `''` as an escaped quote
+    fn string(&mut self) -> Option<String> {
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            match self.peek()? {
+                '\'' if self.chars.get(self.pos + 1) == Some(&'\'') => {
+                    out.push('\'');
+                    self.pos += 2;
+                }
+                '\'' => {
+                    self.pos += 1;
+                    return Some(out);
+                }
+                c => {
+                    out.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Oracle number text (`42`, `-1.5`, `.5`, `1.2E+003`) as a JSON number
+fn parse_number(text: &str) -> Option<Value> {
+    let text = text.trim();
+    if let Ok(i) = text.parse::<i64>() {
+        return Some(Value::from(i));
+    }
+    text.parse::<f64>().ok().and_then(Number::from_f64).map(Value::Number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn catalog() -> TypeCatalog {
+        let mut catalog = TypeCatalog::default();
+        catalog.add_rows(&[
+            json!({"type_owner": "HR", "type_name": "ADDRESS_T", "kind": "OBJECT", "pos": 1, "attr_name": "STREET", "elem_type": "VARCHAR2", "current_schema": "HR"}),
+            json!({"type_owner": "HR", "type_name": "ADDRESS_T", "kind": "OBJECT", "pos": 2, "attr_name": "ZIP", "elem_type": "NUMBER", "precision": 5, "scale": 0}),
+            json!({"type_owner": "HR", "type_name": "ADDRESS_T", "kind": "OBJECT", "pos": 3, "attr_name": "PHONES", "elem_owner": "HR", "elem_type": "PHONES_T"}),
+            json!({"type_owner": "HR", "type_name": "PHONES_T", "kind": "COLLECTION", "pos": 1, "attr_name": null, "elem_type": "VARCHAR2"}),
+            // Same name in another schema
+            json!({"type_owner": "SALES", "type_name": "PHONES_T", "kind": "COLLECTION", "pos": 1, "attr_name": null, "elem_type": "NUMBER"}),
+            json!({"type_owner": "SALES", "type_name": "RATES_T", "kind": "COLLECTION", "pos": 1, "attr_name": null, "elem_type": "NUMBER", "precision": 5, "scale": 2}),
+        ]);
+        catalog
+    }
+
+    #[test]
+    fn test_catalog_shapes() {
+        let catalog = catalog();
+        assert_eq!(
+            catalog.shape("HR.ADDRESS_T"),
+            Some(Shape::Struct(vec![
+                ("STREET".to_string(), Shape::String),
+                ("ZIP".to_string(), Shape::Int),
+                ("PHONES".to_string(), Shape::List(Box::new(Shape::String))),
+            ]))
+        );
+        assert_eq!(catalog.shape("VARCHAR2"), None);
+        assert_eq!(catalog.shape("SALES.PHONES_T"), Some(Shape::List(Box::new(Shape::String))));
+        assert_eq!(catalog.shape("SALES.RATES_T"), Some(Shape::List(Box::new(Shape::Float))));
+        assert_eq!(catalog.shape("PHONES_T"), Some(Shape::List(Box::new(Shape::String))));
+        assert!(is_builtin_type("TIMESTAMP(6) WITH TIME ZONE"));
+        assert_eq!(
+            catalog.missing_types(["ADDRESS_T", "ORDER_T", "SALES.ADDRESS_T", "NUMBER"]),
+            vec!["HR.ORDER_T".to_string(), "SALES.ADDRESS_T".to_string()]
+        );
+        assert_eq!(TypeCatalog::default().missing_types(["\"HR\".\"ADDRESS_T\"", "ORDER_T"]), vec!["HR.ADDRESS_T", "ORDER_T"]);
+
+        let query = TypeCatalog::definition_query(&["HR.ADDRESS_T".to_string(), "ORDER_T".to_string()]);
+        assert!(query.contains("(OWNER = 'HR' AND TYPE_NAME = 'ADDRESS_T') OR (OWNER = SYS_CONTEXT('USERENV', 'CURRENT_SCHEMA') AND TYPE_NAME = 'ORDER_T')"));
+    }
+
+    #[test]
+    fn test_conform_constructor_text() {
+        let shape = catalog().shape("ADDRESS_T").unwrap();
+        let value = json!("HR.ADDRESS_T('1 Main St, ''A''', 75001, HR.PHONES_T('555', NULL))");
+        assert_eq!(
+            conform(&shape, &value),
+            json!({"STREET": "1 Main St, 'A'", "ZIP": 75001, "PHONES": ["555", null]})
+        );
+        // Objects keyed by attribute name, missing attributes are null
+        assert_eq!(
+            conform(&shape, &json!({"street": "x", "zip": "12"})),
+            json!({"STREET": "x", "ZIP": 12, "PHONES": null})
+        );
+        assert_eq!(parse_constructor("PHONES_T()"), Some(json!([])));
+
+        // NUMBER without precision keeps every digit, NUMBER(p,s) is a float
+        let numbers = catalog().shape("SALES.PHONES_T").unwrap();
+        let value = conform(&numbers, &json!("SALES.PHONES_T(9007199254740993, 123456789012345678901234567890, 1.5)"));
+        assert_eq!(value, json!([9007199254740993i64, "123456789012345678901234567890", "1.5"]));
+        let rates = catalog().shape("SALES.RATES_T").unwrap();
+        assert_eq!(conform(&rates, &json!("RATES_T(1.25, 2)")), json!([1.25, 2]));
+        assert_eq!(parse_constructor("not a constructor"), None);
+    }
+}
@@ -0,0 +1,344 @@
+use anyhow::Result;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Where `--table` mode writes the rows of a partitioned table
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum PartitionOutput {
+    /// One file at the output path with the rows of every partition
+    #[default]
+    Merged,
+    /// One file per partition (or subpartition) in the output directory
+    PerPartition,
+}
+
+/// Options of `--table` mode
+#[derive(Debug, Clone)]
+pub struct TableExport {
+    pub table: TableRef,
+    pub output: PartitionOutput,
+    /// Units exported at the same time
+    pub parallel: usize,
+    /// Skip units completed by an earlier run
+    pub resume: bool,
+}
+
+/// A table named with `--table`, `TABLE` or `OWNER.TABLE`
+///
+/// Unquoted names are upper-cased as Oracle does; `"Quoted"` names are kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableRef {
+    pub owner: Option<String>,
+    pub name: String,
+}
+
+impl TableRef {
+    pub fn parse(spec: &str) -> Result<Self> {
+        let parts = split_identifier(spec.trim());
+        let parts: Vec<String> = parts.iter().map(|p| normalize_identifier(p)).collect::<Result<_>>()?;
+        match parts.as_slice() {
+            [name] => Ok(Self { owner: None, name: name.clone() }),
+            [owner, name] => Ok(Self { owner: Some(owner.clone()), name: name.clone() }),
+            _ => anyhow::bail!("Invalid table '{}' (expected TABLE or OWNER.TABLE)", spec),
+        }
+    }
+
+    /// Quoted name for a FROM clause
+    pub fn sql_name(&self) -> String {
+        match &self.owner {
+            Some(owner) => format!("\"{}\".\"{}\"", owner, self.name),
+            None => format!("\"{}\"", self.name),
+        }
+    }
+
+    /// Dictionary filter on the owner column (`owner_column`) and TABLE_NAME;
+    /// without an owner, the current schema (which `--session-init` may change)
+    fn dictionary_filter(&self, owner_column: &str) -> String {
+        let owner = match &self.owner {
+            Some(owner) => format!("'{}'", owner),
+            None => "SYS_CONTEXT('USERENV', 'CURRENT_SCHEMA')".to_string(),
+        };
+        format!("{} = {} AND TABLE_NAME = '{}'", owner_column, owner, self.name)
+    }
+
+    /// Partitions in position order, with their number of subpartitions
+    pub fn partitions_query(&self) -> String {
+        format!(
+            "SELECT PARTITION_NAME, PARTITION_POSITION, SUBPARTITION_COUNT FROM ALL_TAB_PARTITIONS \
+             WHERE {} ORDER BY PARTITION_POSITION",
+            self.dictionary_filter("TABLE_OWNER")
+        )
+    }
+
+    /// Subpartitions of a composite-partitioned table
+    pub fn subpartitions_query(&self) -> String {
+        format!(
+            "SELECT PARTITION_NAME, SUBPARTITION_NAME, SUBPARTITION_POSITION FROM ALL_TAB_SUBPARTITIONS \
+             WHERE {} ORDER BY PARTITION_NAME, SUBPARTITION_POSITION",
+            self.dictionary_filter("TABLE_OWNER")
+        )
+    }
+
+    /// Declared types of the columns, which SQLcl reports without their
+    /// precision, scale or length
+    pub fn columns_query(&self) -> String {
+        format!(
+            "SELECT COLUMN_NAME, DATA_TYPE, DATA_LENGTH, DATA_PRECISION, DATA_SCALE FROM ALL_TAB_COLUMNS \
+             WHERE {} ORDER BY COLUMN_ID",
+            self.dictionary_filter("OWNER")
+        )
+    }
+}
+
+impl fmt::Display for TableRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.owner {
+            Some(owner) => write!(f, "{}.{}", owner, self.name),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// Split `a.b` on dots outside double quotes
+fn split_identifier(spec: &str) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut quoted = false;
+    for c in spec.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                parts.last_mut().unwrap().push(c);
+            }
+            '.' if !quoted => parts.push(String::new()),
+            _ => parts.last_mut().unwrap().push(c),
+        }
+    }
+    parts
+}
+
+fn normalize_identifier(part: &str) -> Result<String> {
+    let part = part.trim();
+    let name = match part.strip_prefix('"').and_then(|p| p.strip_suffix('"')) {
+        Some(quoted) => quoted.to_string(),
+        None => part.to_uppercase(),
+    };
+    if name.is_empty() || name.contains(['"', '\'']) {
+        anyhow::bail!("Invalid identifier '{}'", part);
+    }
+    Ok(name)
+}
+
+/// One unit of work of `--table` mode
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Unit {
+    /// A table without partitions
+    Table,
+    Partition(String),
+    Subpartition(String),
+}
+
+impl Unit {
+    /// FROM clause reading the rows of this unit, as of `scn` when given
+    /// (flashback query clause after the partition extension clause)
+    pub fn source(&self, table: &TableRef, scn: Option<u64>) -> String {
+        let mut from = match self {
+            Unit::Table => table.sql_name(),
+            Unit::Partition(name) => format!("{} PARTITION (\"{}\")", table.sql_name(), name),
+            Unit::Subpartition(name) => format!("{} SUBPARTITION (\"{}\")", table.sql_name(), name),
+        };
+        if let Some(scn) = scn {
+            from.push_str(&format!(" AS OF SCN {}", scn));
+        }
+        from
+    }
+
+    /// File name (without extension) for the output or spool of this unit
+    pub fn file_stem(&self, table: &TableRef) -> String {
+        let name = match self {
+            Unit::Table => &table.name,
+            Unit::Partition(name) | Unit::Subpartition(name) => name,
+        };
+        name.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '$' || c == '#' { c } else { '_' })
+            .collect()
+    }
+
+    /// `--rejects` file of this unit in per-partition mode: `rejects.ndjson`
+    /// becomes `rejects.<unit>.ndjson`
+    pub fn rejects_path(&self, table: &TableRef, rejects: &Path) -> PathBuf {
+        let stem = rejects.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let extension = rejects.extension().map(|e| e.to_string_lossy().to_string()).unwrap_or_else(|| "ndjson".to_string());
+        rejects.with_file_name(format!("{}.{}.{}", stem, self.file_stem(table), extension))
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Unit::Table => write!(f, "table"),
+            Unit::Partition(name) => write!(f, "partition {}", name),
+            Unit::Subpartition(name) => write!(f, "subpartition {}", name),
+        }
+    }
+}
+
+/// Units of a table from the rows of `partitions_query` and `subpartitions_query`
+///
+/// Subpartitions replace their partition and follow the partition order; a
+/// table without partitions is a single unit.
+pub fn units_from_rows(partitions: &[Value], subpartitions: &[Value]) -> Vec<Unit> {
+    let text = |row: &Value, key: &str| row.get(key).and_then(Value::as_str).map(str::to_string);
+
+    let mut units = Vec::new();
+    for partition in partitions {
+        let Some(name) = text(partition, "partition_name") else { continue };
+        let subs: Vec<Unit> = subpartitions
+            .iter()
+            .filter(|row| text(row, "partition_name").as_deref() == Some(name.as_str()))
+            .filter_map(|row| text(row, "subpartition_name"))
+            .map(Unit::Subpartition)
+            .collect();
+
+        if subs.is_empty() {
+            units.push(Unit::Partition(name));
+        } else {
+            units.extend(subs);
+        }
+    }
+
+    if units.is_empty() {
+        units.push(Unit::Table);
+    }
+    units
+}
+
+/// Fail if two units would write the same file
+///
+/// `file_stem` replaces characters that are not safe in file names, so
+/// `"P-1"` and `"P_1"` both become `P_1`; names differing only in case
+/// collide on case-insensitive file systems.
+pub fn check_file_stems(units: &[Unit], table: &TableRef) -> Result<()> {
+    let mut seen: HashMap<String, &Unit> = HashMap::new();
+    for unit in units {
+        let stem = unit.file_stem(table);
+        if let Some(other) = seen.insert(stem.to_lowercase(), unit) {
+            anyhow::bail!(
+                "The {} and {} of {} would both be written to the file name '{}'",
+                other, unit, table, stem
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Declared type of each column from the rows of `columns_query`, written as
+/// in DDL: `NUMBER(10,0)`, `RAW(16)`, `TIMESTAMP(9)`
+///
+/// NUMBER without a precision (or FLOAT) stays `NUMBER`.
+pub fn declared_types(rows: &[Value]) -> HashMap<String, String> {
+    let text = |row: &Value, key: &str| row.get(key).and_then(Value::as_str).map(str::to_string);
+    let number = |row: &Value, key: &str| match row.get(key) {
+        Some(Value::Number(n)) => n.as_i64(),
+        Some(Value::String(s)) => s.trim().parse().ok(),
+        _ => None,
+    };
+
+    let mut types = HashMap::new();
+    for row in rows {
+        let (Some(name), Some(data_type)) = (text(row, "column_name"), text(row, "data_type")) else { continue };
+        let declared = match data_type.as_str() {
+            "NUMBER" => match (number(row, "data_precision"), number(row, "data_scale")) {
+                (Some(precision), Some(scale)) => format!("NUMBER({},{})", precision, scale),
+                _ => data_type,
+            },
+            "RAW" => match number(row, "data_length") {
+                Some(length) => format!("RAW({})", length),
+                None => data_type,
+            },
+            _ => data_type,
+        };
+        types.insert(name, declared);
+    }
+    types
+}
+
+/// Whether any partition has subpartitions
+pub fn has_subpartitions(partitions: &[Value]) -> bool {
+    partitions.iter().any(|row| match row.get("subpartition_count") {
+        Some(Value::Number(n)) => n.as_u64().is_some_and(|count| count > 0),
+        Some(Value::String(s)) => s.trim().parse::<u64>().is_ok_and(|count| count > 0),
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_tables_and_units() {
+        let table = TableRef::parse("sales.\"Fact.Orders\"").unwrap();
+        assert_eq!(table, TableRef { owner: Some("SALES".to_string()), name: "Fact.Orders".to_string() });
+        assert_eq!(table.sql_name(), "\"SALES\".\"Fact.Orders\"");
+        assert!(TableRef::parse("a.b.c").is_err());
+        assert!(TableRef::parse("x'; DROP").is_err());
+        assert!(TableRef::parse("orders").unwrap().partitions_query().contains("SYS_CONTEXT('USERENV', 'CURRENT_SCHEMA')"));
+
+        let partitions = vec![
+            json!({"partition_name": "P2024", "partition_position": 1, "subpartition_count": 2}),
+            json!({"partition_name": "P2025", "partition_position": 2, "subpartition_count": 0}),
+        ];
+        let subpartitions = vec![
+            json!({"partition_name": "P2024", "subpartition_name": "P2024_EU", "subpartition_position": 1}),
+            json!({"partition_name": "P2024", "subpartition_name": "P2024_US", "subpartition_position": 2}),
+        ];
+        assert!(has_subpartitions(&partitions));
+        let units = units_from_rows(&partitions, &subpartitions);
+        assert_eq!(
+            units,
+            vec![
+                Unit::Subpartition("P2024_EU".to_string()),
+                Unit::Subpartition("P2024_US".to_string()),
+                Unit::Partition("P2025".to_string()),
+            ]
+        );
+        assert_eq!(units[2].source(&table, None), "\"SALES\".\"Fact.Orders\" PARTITION (\"P2025\")");
+        assert_eq!(
+            units[0].source(&table, Some(42)),
+            "\"SALES\".\"Fact.Orders\" SUBPARTITION (\"P2024_EU\") AS OF SCN 42"
+        );
+        assert_eq!(units_from_rows(&[], &[]), vec![Unit::Table]);
+        assert_eq!(Unit::Table.file_stem(&table), "Fact_Orders");
+        assert!(check_file_stems(&units, &table).is_ok());
+        let colliding = [Unit::Partition("P-1".to_string()), Unit::Partition("P_1".to_string())];
+        assert!(check_file_stems(&colliding, &table).is_err());
+        let case_only = [Unit::Partition("p1".to_string()), Unit::Partition("P1".to_string())];
+        assert!(check_file_stems(&case_only, &table).is_err());
+        assert_eq!(
+            units[0].rejects_path(&table, Path::new("out/rejects.ndjson")),
+            PathBuf::from("out/rejects.P2024_EU.ndjson")
+        );
+    }
+
+    #[test]
+    fn test_declared_types() {
+        assert!(TableRef::parse("sales.orders").unwrap().columns_query().contains("OWNER = 'SALES' AND TABLE_NAME = 'ORDERS'"));
+
+        let rows = vec![
+            json!({"column_name": "ID", "data_type": "NUMBER", "data_length": 22, "data_precision": 10, "data_scale": 0}),
+            json!({"column_name": "AMOUNT", "data_type": "NUMBER", "data_length": 22, "data_precision": "12", "data_scale": "2"}),
+            json!({"column_name": "SCORE", "data_type": "NUMBER", "data_length": 22, "data_precision": null, "data_scale": null}),
+            json!({"column_name": "GUID", "data_type": "RAW", "data_length": 16, "data_precision": null, "data_scale": null}),
+            json!({"column_name": "AT", "data_type": "TIMESTAMP(9)", "data_length": 11, "data_precision": null, "data_scale": 9}),
+        ];
+        let types = declared_types(&rows);
+        assert_eq!(types["ID"], "NUMBER(10,0)");
+        assert_eq!(types["AMOUNT"], "NUMBER(12,2)");
+        assert_eq!(types["SCORE"], "NUMBER");
+        assert_eq!(types["GUID"], "RAW(16)");
+        assert_eq!(types["AT"], "TIMESTAMP(9)");
+    }
+}
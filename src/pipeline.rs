@@ -1,30 +1,96 @@
 use anyhow::{Context, Result};
-use std::path::Path;
+use futures::TryStreamExt;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use tokio::fs;
 
-use crate::json_stream::JsonStreamReader;
+use crate::json_stream::{ColumnInfo, JsonStreamReader, QueryResult};
+use crate::lobs;
+use crate::object_types::TypeCatalog;
+use crate::partitions::{self, PartitionOutput, TableExport, TableRef, Unit};
+use crate::snapshot::{self, SavedSnapshot, Snapshot};
 use crate::sqlcl::{SqlclConfig, SqlclProcess};
-use crate::vortex_writer::VortexWriter;
+use crate::vortex_writer::{VortexWriter, WriterConfig};
+
+/// File of the progress directory of `--table` mode holding the snapshot
+const SNAPSHOT_FILE: &str = "snapshot";
 
 pub struct Pipeline {
     config: SqlclConfig,
     batch_size: usize,
     auto_batch_rows: usize,
-    skip_lobs: bool,
+    writer_config: WriterConfig,
+    snapshot: Snapshot,
+    /// SCN pinned for `--table` mode, added to the FROM clause of every unit
+    as_of_scn: Option<u64>,
+    /// Declared column types of the `--table` table, by column name
+    declared_types: HashMap<String, String>,
 }
 
 impl Pipeline {
-    pub fn new(config: SqlclConfig, batch_size: usize, auto_batch_rows: usize, skip_lobs: bool) -> Self {
+    pub fn new(
+        config: SqlclConfig,
+        batch_size: usize,
+        auto_batch_rows: usize,
+        writer_config: WriterConfig,
+        snapshot: Snapshot,
+    ) -> Self {
         Self { 
             config, 
             batch_size,
             auto_batch_rows,
-            skip_lobs,
+            writer_config,
+            snapshot,
+            as_of_scn: None,
+            declared_types: HashMap::new(),
+        }
+    }
+
+    /// Resolve the snapshot to the SCN every data query must read
+    ///
+    /// With `Snapshot::Auto`, the current SCN is captured only when the export
+    /// runs several data queries. An SCN that cannot be read or converted is an
+    /// error; `--no-snapshot` exports without one.
+    async fn pin_snapshot(&self, several_queries: bool) -> Result<Option<u64>> {
+        let scn = match self.snapshot {
+            Snapshot::Off => return Ok(None),
+            Snapshot::Auto if !several_queries => return Ok(None),
+            Snapshot::Auto => self.current_scn().await.context(
+                "Could not read the current SCN to run every query on the same data \
+                 (use --no-snapshot to export without a consistent snapshot)",
+            )?,
+            Snapshot::Scn(scn) => scn,
+            Snapshot::Timestamp(at) => {
+                let rows = self.fetch_rows(&snapshot::timestamp_scn_query(&at)).await?;
+                snapshot::scn_from_rows(&rows)
+                    .with_context(|| format!("No SCN found for --as-of-timestamp {}", at))?
+            }
+        };
+
+        tracing::info!("Reading data as of SCN {}", scn);
+        Ok(Some(scn))
+    }
+
+    /// Current SCN of the database
+    async fn current_scn(&self) -> Result<u64> {
+        let mut last_error = None;
+        for query in snapshot::CURRENT_SCN_QUERIES {
+            match self.fetch_rows(query).await {
+                Ok(rows) => {
+                    if let Some(scn) = snapshot::scn_from_rows(&rows) {
+                        return Ok(scn);
+                    }
+                    last_error = Some(anyhow::anyhow!("'{}' returned no SCN", query));
+                }
+                Err(e) => last_error = Some(e),
+            }
         }
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("no SCN query")))
     }
 
-    /// Prepare SQL query for batching by wrapping with OFFSET/FETCH
-    fn wrap_query_with_offset(&self, base_query: &str, offset: usize, fetch_rows: usize) -> String {
+    /// Strip comment lines and the trailing semicolon so the query can be nested
+    /// in a subquery
+    fn clean_query(base_query: &str) -> String {
         // Remove comments (lines starting with --)
         let cleaned_query: Vec<&str> = base_query
             .lines()
@@ -37,26 +103,186 @@ impl Pipeline {
         let base_query = cleaned_query.join("\n");
         
         // Remove trailing semicolon and whitespace
-        let base_query = base_query.trim().trim_end_matches(';').trim();
+        base_query.trim().trim_end_matches(';').trim().to_string()
+    }
+
+    /// Prepare SQL query for batching by adding OFFSET/FETCH after its ORDER BY
+    ///
+    /// Without an ORDER BY, Oracle may return the rows of each batch in a
+    /// different order, so pages could overlap or miss rows: the query is
+    /// wrapped as is with a warning, or refused with `--strict`.
+    fn wrap_query_with_offset(&self, base_query: &str, offset: usize, fetch_rows: usize) -> Result<String> {
+        let base_query = Self::clean_query(base_query);
         
         // Check if query already has OFFSET/FETCH
         let base_upper = base_query.to_uppercase();
         if base_upper.contains("OFFSET") && base_upper.contains("FETCH") {
             tracing::warn!("Query already contains OFFSET/FETCH, using as-is");
-            return base_query.to_string();
+            return Ok(base_query);
+        }
+
+        if split_order_by(&base_query).1.is_none() {
+            if self.writer_config.strict {
+                anyhow::bail!(
+                    "--auto-batch-rows with --strict needs a query ending with ORDER BY (on a unique key): \
+                     OFFSET/FETCH batches of an unordered query may overlap or miss rows"
+                );
+            }
+            if offset == 0 {
+                tracing::warn!(
+                    "Query has no ORDER BY: OFFSET/FETCH batches may overlap or miss rows; \
+                     end the query with ORDER BY on a unique key"
+                );
+            }
+
+            // Wrap with OFFSET/FETCH (Oracle 12c+ syntax)
+            return Ok(format!(
+                "SELECT * FROM (\n{}\n) \nOFFSET {} ROWS FETCH NEXT {} ROWS ONLY",
+                base_query,
+                offset,
+                fetch_rows
+            ));
         }
         
-        // Wrap with OFFSET/FETCH (Oracle 12c+ syntax)
-        format!(
-            "SELECT * FROM (\n{}\n) \nOFFSET {} ROWS FETCH NEXT {} ROWS ONLY",
+        // Row limiting clause after the ORDER BY (Oracle 12c+ syntax)
+        Ok(format!(
+            "{}\nOFFSET {} ROWS FETCH NEXT {} ROWS ONLY",
             base_query,
             offset,
             fetch_rows
-        )
+        ))
+    }
+
+    /// Run the query without fetching rows to get its column metadata
+    async fn describe_query(&self, sql_query: &str) -> Result<Vec<ColumnInfo>> {
+        let probe = format!("SELECT * FROM (\n{}\n) WHERE 1 = 0", Self::clean_query(sql_query));
+
+        let result = SqlclProcess::spawn(&self.config, &probe).await?.read_json().await?;
+        Ok(result.columns)
+    }
+
+    /// Look up the definitions of the object types and collections used by `columns`
+    ///
+    /// Attribute and element types are resolved in turn, one query per level of
+    /// nesting. A failed lookup is logged and leaves those columns as strings.
+    async fn load_type_catalog(&self, columns: &[ColumnInfo]) -> TypeCatalog {
+        let mut catalog = TypeCatalog::default();
+        let mut missing = catalog.missing_types(columns.iter().map(|c| c.oracle_type.as_str()));
+
+        while !missing.is_empty() {
+            tracing::info!("Looking up type definitions: {}", missing.join(", "));
+            let rows = match self.fetch_rows(&TypeCatalog::definition_query(&missing)).await {
+                Ok(rows) => rows,
+                Err(e) => {
+                    tracing::warn!("Could not read type definitions ({:#}), object columns stay strings", e);
+                    break;
+                }
+            };
+            catalog.add_rows(&rows);
+
+            let still_missing = catalog.missing_types(columns.iter().map(|c| c.oracle_type.as_str()));
+            if still_missing.iter().any(|name| missing.contains(name)) {
+                tracing::warn!("No definition found for types: {}", still_missing.join(", "));
+                break;
+            }
+            missing = still_missing;
+        }
+
+        catalog
+    }
+
+    /// Declared types of the columns of a table, from the data dictionary
+    ///
+    /// A failed lookup is logged: NUMBER columns then keep their inferred width.
+    async fn load_declared_types(&self, table: &TableRef) -> HashMap<String, String> {
+        match self.fetch_rows(&table.columns_query()).await {
+            Ok(rows) => partitions::declared_types(&rows),
+            Err(e) => {
+                tracing::warn!("Could not read the column types of {} ({:#}), numbers keep their inferred width", table, e);
+                HashMap::new()
+            }
+        }
+    }
+
+    /// Columns with the type names SQLcl reports bare (`NUMBER`, `RAW`,
+    /// `TIMESTAMP`) replaced by their declared type (`NUMBER(10,0)`)
+    fn declared_columns(&self, columns: &[ColumnInfo]) -> Vec<ColumnInfo> {
+        columns
+            .iter()
+            .map(|column| match self.declared_types.get(&column.name) {
+                Some(declared)
+                    if !column.oracle_type.contains('(')
+                        && declared
+                            .to_uppercase()
+                            .starts_with(&format!("{}(", column.oracle_type.to_uppercase())) =>
+                {
+                    ColumnInfo { name: column.name.clone(), oracle_type: declared.clone() }
+                }
+                _ => column.clone(),
+            })
+            .collect()
+    }
+
+    /// Run a query and return its rows
+    async fn fetch_rows(&self, sql_query: &str) -> Result<Vec<serde_json::Value>> {
+        let result = SqlclProcess::spawn(&self.config, sql_query).await?.read_json().await?;
+        Ok(result.records)
+    }
+
+    /// `DBMS_LOB.GETLENGTH` columns (`<COL>__LOB_LENGTH`) for the LOB columns,
+    /// read through the table alias `alias`
+    fn lob_length_columns(alias: &str, columns: &[ColumnInfo]) -> Vec<String> {
+        columns
+            .iter()
+            .filter(|c| lobs::is_lob_type(&c.oracle_type))
+            .map(|c| {
+                format!(
+                    "DBMS_LOB.GETLENGTH({}.\"{}\") AS \"{}{}\"",
+                    alias, c.name, c.name, lobs::LOB_LENGTH_SUFFIX
+                )
+            })
+            .collect()
+    }
+
+    /// Add a `DBMS_LOB.GETLENGTH` column (`<COL>__LOB_LENGTH`) for each LOB column
+    /// of the query, so the writer can detect values truncated by SQLcl
+    ///
+    /// A final ORDER BY moves to the outer query so auto-batching keeps it; it
+    /// must then only name output columns.
+    fn with_lob_lengths(sql_query: &str, columns: &[ColumnInfo]) -> Option<String> {
+        let lengths = Self::lob_length_columns("q", columns);
+        if lengths.is_empty() {
+            return None;
+        }
+
+        let query = Self::clean_query(sql_query);
+        let (body, order_by) = split_order_by(&query);
+        Some(format!(
+            "SELECT q.*, {} FROM (\n{}\n) q{}",
+            lengths.join(", "),
+            body,
+            order_by.map(|o| format!("\n{}", o)).unwrap_or_default()
+        ))
+    }
+
+    /// Query reading one unit of `--table` mode, as of the pinned SCN
+    ///
+    /// With auto-batching the rows are read in ROWID order, so that the
+    /// OFFSET/FETCH batches of a unit do not overlap.
+    async fn unit_query(&self, table: &TableRef, unit: &Unit) -> Result<String> {
+        let from = unit.source(table, self.as_of_scn);
+        let mut select = vec!["t.*".to_string()];
+        if self.writer_config.verify_lob_lengths {
+            let columns = self.describe_query(&format!("SELECT * FROM {}", from)).await?;
+            select.extend(Self::lob_length_columns("t", &columns));
+        }
+
+        let order_by = if self.auto_batch_rows > 0 { " ORDER BY t.ROWID" } else { "" };
+        Ok(format!("SELECT {} FROM {} t{}", select.join(", "), from, order_by))
     }
 
     pub async fn run<P: AsRef<Path>, Q: AsRef<Path>>(
-        &self,
+        &mut self,
         sql_file: P,
         output_file: Q,
     ) -> Result<()> {
@@ -67,44 +293,299 @@ impl Pipeline {
 
         tracing::info!("SQL query loaded: {} bytes", sql_query.len());
 
+        // Free-form queries cannot take an AS OF clause: the whole session reads the SCN
+        self.config.as_of_scn = self.pin_snapshot(self.auto_batch_rows > 0).await?;
+        let sql_query = self.prepare_query(&sql_query).await?;
+        self.export_query(&sql_query, output_file.as_ref(), self.writer_config.clone()).await?;
+        Ok(())
+    }
+
+    /// Add the LOB length columns when `--verify-lob-lengths` is given
+    async fn prepare_query(&self, sql_query: &str) -> Result<String> {
+        if !self.writer_config.verify_lob_lengths {
+            return Ok(sql_query.to_string());
+        }
+
+        let columns = self.describe_query(sql_query).await?;
+        Ok(match Self::with_lob_lengths(sql_query, &columns) {
+            Some(query) => {
+                tracing::info!("Verifying LOB lengths with DBMS_LOB.GETLENGTH");
+                query
+            }
+            None => {
+                tracing::info!("--verify-lob-lengths: query has no LOB columns");
+                sql_query.to_string()
+            }
+        })
+    }
+
+    /// Export the rows of one query to one Vortex file, returning the number of rows
+    async fn export_query(&self, sql_query: &str, output_file: &Path, writer_config: WriterConfig) -> Result<usize> {
         if self.auto_batch_rows == 0 {
             // Regular single-query mode
-            self.run_single_query(&sql_query, output_file).await
+            self.run_single_query(sql_query, output_file, writer_config).await
         } else {
             // Auto-batching mode
-            self.run_auto_batched(&sql_query, output_file).await
+            self.run_auto_batched(sql_query, output_file, writer_config).await
+        }
+    }
+
+    /// Export a table (`--table`), one unit of work per partition or subpartition
+    ///
+    /// Units run `parallel` at a time. In per-partition mode each unit is written
+    /// to `<output>/<PARTITION>.vortex`; in merged mode each unit is first spooled
+    /// to `<output>.parts/<PARTITION>.json`, then all rows are written to the
+    /// output file. The progress directory (`<output>/.parts` in per-partition
+    /// mode, the spool directory in merged mode) also keeps the pinned SCN and
+    /// the units without rows, and is removed once the export is complete.
+    /// With `resume`, completed units are not exported again and the SCN of the
+    /// interrupted run is reused.
+    pub async fn run_table<Q: AsRef<Path>>(&mut self, export: &TableExport, output_path: Q) -> Result<()> {
+        let output_path = output_path.as_ref();
+        let units = self.list_units(export).await?;
+        tracing::info!("Table {}: {} units of work", export.table, units.len());
+        partitions::check_file_stems(&units, &export.table)?;
+
+        let progress_dir = match export.output {
+            PartitionOutput::PerPartition => output_path.join(".parts"),
+            PartitionOutput::Merged => PathBuf::from(format!("{}.parts", output_path.display())),
+        };
+        if !export.resume && fs::try_exists(&progress_dir).await.unwrap_or(false) {
+            fs::remove_dir_all(&progress_dir)
+                .await
+                .with_context(|| format!("Failed to remove {:?}", progress_dir))?;
+        }
+        fs::create_dir_all(&progress_dir)
+            .await
+            .with_context(|| format!("Failed to create directory {:?}", progress_dir))?;
+
+        self.as_of_scn = self
+            .table_snapshot(export, &progress_dir, units.len() > 1 || self.auto_batch_rows > 0)
+            .await?;
+        self.declared_types = self.load_declared_types(&export.table).await;
+
+        match export.output {
+            PartitionOutput::PerPartition => self.export_partitions(export, &units, output_path, &progress_dir).await?,
+            PartitionOutput::Merged => self.export_merged(export, &units, output_path, &progress_dir).await?,
+        }
+
+        fs::remove_dir_all(&progress_dir)
+            .await
+            .with_context(|| format!("Failed to remove {:?}", progress_dir))?;
+        tracing::info!("Pipeline completed successfully");
+        Ok(())
+    }
+
+    /// SCN of a `--table` export, kept in the progress directory with the
+    /// snapshot option so that a resumed run reads the same data as the units
+    /// already exported
+    ///
+    /// A resume with other snapshot options (`--as-of-scn`, `--as-of-timestamp`,
+    /// `--no-snapshot`) than the interrupted run is refused.
+    async fn table_snapshot(&self, export: &TableExport, progress_dir: &Path, several_queries: bool) -> Result<Option<u64>> {
+        let file = progress_dir.join(SNAPSHOT_FILE);
+        let option = self.snapshot.to_string();
+        if export.resume {
+            if let Ok(text) = fs::read_to_string(&file).await {
+                let saved = SavedSnapshot::parse(&text)
+                    .with_context(|| format!("Invalid snapshot file {:?}", file))?;
+                if saved.option != option {
+                    anyhow::bail!(
+                        "The interrupted export ran with snapshot '{}', not '{}': resume it with the same \
+                         snapshot options, or run it again without --resume",
+                        saved.option, option
+                    );
+                }
+                match saved.scn {
+                    Some(scn) => tracing::info!("Resuming as of SCN {}", scn),
+                    None => tracing::info!("Resuming without a snapshot"),
+                }
+                return Ok(saved.scn);
+            }
+        }
+
+        let scn = self.pin_snapshot(several_queries).await?;
+        fs::write(&file, SavedSnapshot { option, scn }.to_text())
+            .await
+            .with_context(|| format!("Failed to write {:?}", file))?;
+        Ok(scn)
+    }
+
+    /// Partitions (or subpartitions) of the table, in partition order
+    async fn list_units(&self, export: &TableExport) -> Result<Vec<Unit>> {
+        let table = &export.table;
+        let partitions = self
+            .fetch_rows(&table.partitions_query())
+            .await
+            .with_context(|| format!("Failed to list the partitions of {}", table))?;
+        let subpartitions = if partitions::has_subpartitions(&partitions) {
+            self.fetch_rows(&table.subpartitions_query())
+                .await
+                .with_context(|| format!("Failed to list the subpartitions of {}", table))?
+        } else {
+            Vec::new()
+        };
+
+        Ok(partitions::units_from_rows(&partitions, &subpartitions))
+    }
+
+    async fn export_partitions(&self, export: &TableExport, units: &[Unit], output_dir: &Path, progress_dir: &Path) -> Result<()> {
+        futures::stream::iter(units.iter().map(Ok))
+            .try_for_each_concurrent(export.parallel.max(1), |unit| async move {
+                let stem = unit.file_stem(&export.table);
+                let file = output_dir.join(format!("{}.vortex", stem));
+                // A unit without rows writes no file, only this marker
+                let empty_marker = progress_dir.join(format!("{}.empty", stem));
+                if export.resume && fs::try_exists(&file).await.unwrap_or(false) {
+                    tracing::info!("{}: {:?} already exists, skipping", unit, file);
+                    return Ok(());
+                }
+                if export.resume && fs::try_exists(&empty_marker).await.unwrap_or(false) {
+                    tracing::info!("{}: no rows, skipping", unit);
+                    return Ok(());
+                }
+
+                let mut writer_config = self.writer_config.clone();
+                writer_config.rejects_path = writer_config
+                    .rejects_path
+                    .map(|path| unit.rejects_path(&export.table, &path));
+
+                tracing::info!("{}: exporting to {:?}", unit, file);
+                let query = self.unit_query(&export.table, unit).await?;
+                let count = self.export_query(&query, &file, writer_config)
+                    .await
+                    .with_context(|| format!("Failed to export {} of {}", unit, export.table))?;
+                if count == 0 {
+                    fs::write(&empty_marker, "")
+                        .await
+                        .with_context(|| format!("Failed to write {:?}", empty_marker))?;
+                }
+                Ok(())
+            })
+            .await
+    }
+
+    async fn export_merged(&self, export: &TableExport, units: &[Unit], output_file: &Path, spool_dir: &Path) -> Result<()> {
+        let spool_file = |unit: &Unit| spool_dir.join(format!("{}.json", unit.file_stem(&export.table)));
+
+        // Fetch the units, keeping each one in the spool directory until all are done
+        let spool_file = &spool_file;
+        futures::stream::iter(units.iter().map(Ok))
+            .try_for_each_concurrent(export.parallel.max(1), |unit| async move {
+                let file = spool_file(unit);
+                if export.resume && fs::try_exists(&file).await.unwrap_or(false) {
+                    tracing::info!("{}: already fetched, skipping", unit);
+                    return Ok(());
+                }
+
+                tracing::info!("{}: fetching", unit);
+                let query = self.unit_query(&export.table, unit).await?;
+                let result = self
+                    .fetch_all(&query)
+                    .await
+                    .with_context(|| format!("Failed to export {} of {}", unit, export.table))?;
+                Self::write_spool(&file, &result).await
+            })
+            .await?;
+
+        // Write all units, in partition order, to one file
+        let mut vortex_writer = VortexWriter::new(self.writer_config.clone());
+        let mut total_count = 0;
+        for (idx, unit) in units.iter().enumerate() {
+            let file = spool_file(unit);
+            let text = fs::read_to_string(&file)
+                .await
+                .with_context(|| format!("Failed to read spool file {:?}", file))?;
+            let result = JsonStreamReader::parse_spool(&text)
+                .with_context(|| format!("Invalid spool file {:?}", file))?;
+
+            vortex_writer.start_batch();
+            if idx == 0 {
+                vortex_writer.set_type_catalog(self.load_type_catalog(&result.columns).await);
+            }
+            vortex_writer.set_columns(&self.declared_columns(&result.columns))?;
+            total_count += result.records.len();
+            for record in result.records {
+                vortex_writer.add_record(record).await?;
+            }
+        }
+
+        tracing::info!("Table {}: {} records from {} units", export.table, total_count, units.len());
+        if total_count > 0 {
+            vortex_writer.flush(output_file).await?;
+        } else {
+            tracing::warn!("No records to write");
         }
+        Ok(())
+    }
+
+    /// Fetch all rows of a query, in OFFSET/FETCH batches when auto-batching is on
+    async fn fetch_all(&self, sql_query: &str) -> Result<QueryResult> {
+        if self.auto_batch_rows == 0 {
+            return SqlclProcess::spawn(&self.config, sql_query).await?.read_json().await;
+        }
+
+        let mut all = QueryResult::default();
+        let mut offset = 0;
+        loop {
+            let batched_query = self.wrap_query_with_offset(sql_query, offset, self.auto_batch_rows)?;
+            let result = SqlclProcess::spawn(&self.config, &batched_query).await?.read_json().await?;
+
+            let batch_size = result.records.len();
+            if all.columns.is_empty() {
+                all.columns = result.columns;
+            }
+            all.records.extend(result.records);
+            if batch_size < self.auto_batch_rows {
+                return Ok(all);
+            }
+            offset += self.auto_batch_rows;
+        }
+    }
+
+    /// Write fetched rows in SQLcl's JSON shape, through a temporary file so an
+    /// interrupted run never leaves a partial spool file behind
+    async fn write_spool(file: &Path, result: &QueryResult) -> Result<()> {
+        let columns: Vec<serde_json::Value> = result
+            .columns
+            .iter()
+            .map(|c| serde_json::json!({"name": c.name, "type": c.oracle_type}))
+            .collect();
+        let spool = serde_json::json!({"results": [{"columns": columns, "items": result.records}]});
+
+        let tmp = file.with_extension("json.tmp");
+        fs::write(&tmp, spool.to_string())
+            .await
+            .with_context(|| format!("Failed to write spool file {:?}", tmp))?;
+        fs::rename(&tmp, file)
+            .await
+            .with_context(|| format!("Failed to write spool file {:?}", file))?;
+        Ok(())
     }
 
     async fn run_single_query<Q: AsRef<Path>>(
         &self,
         sql_query: &str,
         output_file: Q,
-    ) -> Result<()> {
+        writer_config: WriterConfig,
+    ) -> Result<usize> {
         tracing::info!("Starting pipeline (JSON format for type preservation)");
         tracing::info!("Batch size setting: {} rows (note: JSON loads all at once)", self.batch_size);
 
-        // Spawn SQLcl process
+        // Spawn SQLcl process and read all JSON
         let mut sqlcl = SqlclProcess::spawn(&self.config, sql_query).await?;
+        let result = sqlcl.read_json().await?;
 
-        // Get stdout stream
-        let stdout = sqlcl.stdout()
-            .context("Failed to get SQLcl stdout")?;
-
-        // Create JSON stream reader
-        let mut json_reader = JsonStreamReader::new(stdout);
-
-        // Read all JSON
-        let records = json_reader.read_all_json().await?;
-
-        tracing::info!("Loaded {} records from SQLcl", records.len());
+        tracing::info!("Loaded {} records from SQLcl", result.records.len());
 
         // Create Vortex writer
-        let mut vortex_writer = VortexWriter::new(self.skip_lobs);
+        let mut vortex_writer = VortexWriter::new(writer_config);
+        vortex_writer.set_type_catalog(self.load_type_catalog(&result.columns).await);
+        vortex_writer.set_columns(&self.declared_columns(&result.columns))?;
 
         // Process records
         let mut count = 0;
-        for record in records {
+        for record in result.records {
             vortex_writer.add_record(record).await?;
             count += 1;
 
@@ -122,24 +603,22 @@ impl Pipeline {
             tracing::warn!("No records to write");
         }
 
-        // Wait for SQLcl to complete
-        sqlcl.wait().await?;
-
         tracing::info!("Pipeline completed successfully");
 
-        Ok(())
+        Ok(count)
     }
 
     async fn run_auto_batched<Q: AsRef<Path>>(
         &self,
         base_sql_query: &str,
         output_file: Q,
-    ) -> Result<()> {
+        writer_config: WriterConfig,
+    ) -> Result<usize> {
         tracing::info!("Starting AUTO-BATCHING mode");
         tracing::info!("Batch size: {} rows per query", self.auto_batch_rows);
 
         // Create Vortex writer for all batches
-        let mut vortex_writer = VortexWriter::new(self.skip_lobs);
+        let mut vortex_writer = VortexWriter::new(writer_config);
         let mut total_count = 0;
         let mut batch_num = 0;
         let mut offset = 0;
@@ -152,21 +631,16 @@ impl Pipeline {
                 base_sql_query, 
                 offset, 
                 self.auto_batch_rows
-            );
+            )?;
 
             tracing::info!("Batch {}: fetching rows {} to {}", 
                 batch_num, offset, offset + self.auto_batch_rows - 1);
             
             // Execute SQLcl for this batch
             let mut sqlcl = SqlclProcess::spawn(&self.config, &batched_query).await?;
+            let result = sqlcl.read_json().await?;
             
-            let stdout = sqlcl.stdout()
-                .context("Failed to get SQLcl stdout")?;
-
-            let mut json_reader = JsonStreamReader::new(stdout);
-            let records = json_reader.read_all_json().await?;
-            
-            let batch_size = records.len();
+            let batch_size = result.records.len();
             tracing::info!("Batch {}: received {} records", batch_num, batch_size);
 
             // If no records, we've reached the end
@@ -176,14 +650,16 @@ impl Pipeline {
             }
 
             // Add records to vortex writer
-            for record in records {
+            vortex_writer.start_batch();
+            if batch_num == 1 {
+                vortex_writer.set_type_catalog(self.load_type_catalog(&result.columns).await);
+            }
+            vortex_writer.set_columns(&self.declared_columns(&result.columns))?;
+            for record in result.records {
                 vortex_writer.add_record(record).await?;
                 total_count += 1;
             }
 
-            // Wait for SQLcl to complete
-            sqlcl.wait().await?;
-
             // If we got fewer records than requested, we're at the end
             if batch_size < self.auto_batch_rows {
                 tracing::info!("Last batch (partial: {} records), stopping", batch_size);
@@ -205,6 +681,178 @@ impl Pipeline {
 
         tracing::info!("Pipeline completed successfully");
 
-        Ok(())
+        Ok(total_count)
+    }
+}
+
+/// Split a query into its body and its final top-level `ORDER BY` clause
+///
+/// ORDER BY inside parentheses (subqueries, analytic functions) or quotes
+/// does not count.
+fn split_order_by(query: &str) -> (&str, Option<&str>) {
+    let is_word = |b: u8| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'$' | b'#');
+    let bytes = query.as_bytes();
+    let upper = query.to_ascii_uppercase();
+
+    let mut depth = 0i32;
+    let mut quote = None;
+    let mut found = None;
+    for (i, &b) in bytes.iter().enumerate() {
+        if let Some(q) = quote {
+            if b == q {
+                quote = None;
+            }
+            continue;
+        }
+        match b {
+            b'\'' | b'"' => quote = Some(b),
+            b'(' => depth += 1,
+            b')' => depth -= 1,
+            b'O' | b'o' if depth == 0 && upper[i..].starts_with("ORDER") && (i == 0 || !is_word(bytes[i - 1])) => {
+                let after = &upper[i + 5..];
+                let rest = after.trim_start();
+                if after.len() > rest.len()
+                    && rest.starts_with("BY")
+                    && !rest.as_bytes().get(2).is_some_and(|&c| is_word(c))
+                {
+                    found = Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    match found {
+        Some(i) => (query[..i].trim_end(), Some(&query[i..])),
+        None => (query, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_order_by() {
+        assert_eq!(
+            split_order_by("SELECT * FROM t ORDER BY id"),
+            ("SELECT * FROM t", Some("ORDER BY id"))
+        );
+        assert_eq!(
+            split_order_by("SELECT ROW_NUMBER() OVER (ORDER BY a) rn, 'order by' s FROM (SELECT * FROM t ORDER BY b)"),
+            ("SELECT ROW_NUMBER() OVER (ORDER BY a) rn, 'order by' s FROM (SELECT * FROM t ORDER BY b)", None)
+        );
+        assert_eq!(split_order_by("SELECT border, recorder FROM t").1, None);
+        assert_eq!(split_order_by("select * from t\norder\n  by x desc").1, Some("order\n  by x desc"));
+    }
+
+    fn pipeline(auto_batch_rows: usize, writer_config: WriterConfig, snapshot: Snapshot) -> Pipeline {
+        let config = SqlclConfig {
+            connection_string: String::new(),
+            sqlcl_path: "sql".to_string(),
+            thick: false,
+            lob_fetch: Default::default(),
+            session_init: Default::default(),
+            as_of_scn: None,
+        };
+        Pipeline::new(config, 1000, auto_batch_rows, writer_config, snapshot)
+    }
+
+    #[test]
+    fn test_wrap_query_with_offset() {
+        let lenient = pipeline(100, WriterConfig::default(), Snapshot::Auto);
+        assert_eq!(
+            lenient.wrap_query_with_offset("SELECT * FROM t ORDER BY id;", 200, 100).unwrap(),
+            "SELECT * FROM t ORDER BY id\nOFFSET 200 ROWS FETCH NEXT 100 ROWS ONLY"
+        );
+        // Unordered: wrapped with a warning, refused with --strict
+        assert_eq!(
+            lenient.wrap_query_with_offset("SELECT * FROM t", 0, 100).unwrap(),
+            "SELECT * FROM (\nSELECT * FROM t\n) \nOFFSET 0 ROWS FETCH NEXT 100 ROWS ONLY"
+        );
+        let strict = pipeline(100, WriterConfig { strict: true, ..Default::default() }, Snapshot::Auto);
+        assert!(strict.wrap_query_with_offset("SELECT * FROM t", 0, 100).is_err());
+        assert!(strict.wrap_query_with_offset("SELECT * FROM t ORDER BY id", 0, 100).is_ok());
+    }
+
+    #[test]
+    fn test_declared_columns() {
+        let mut pipeline = pipeline(0, WriterConfig::default(), Snapshot::Auto);
+        pipeline.declared_types = partitions::declared_types(&[
+            serde_json::json!({"column_name": "ID", "data_type": "NUMBER", "data_precision": 9, "data_scale": 0}),
+            serde_json::json!({"column_name": "GUID", "data_type": "RAW", "data_length": 16}),
+            serde_json::json!({"column_name": "AT", "data_type": "TIMESTAMP(3)"}),
+        ]);
+        let column = |name: &str, oracle_type: &str| ColumnInfo { name: name.to_string(), oracle_type: oracle_type.to_string() };
+
+        let columns = pipeline.declared_columns(&[
+            column("ID", "NUMBER"),
+            column("GUID", "RAW"),
+            column("AT", "TIMESTAMP(3)"),
+            // Not a column of the table, or not the same type
+            column("ID_TEXT", "VARCHAR2"),
+            column("GUID", "VARCHAR2"),
+        ]);
+        assert_eq!(
+            columns,
+            vec![
+                column("ID", "NUMBER(9,0)"),
+                column("GUID", "RAW(16)"),
+                column("AT", "TIMESTAMP(3)"),
+                column("ID_TEXT", "VARCHAR2"),
+                column("GUID", "VARCHAR2"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resume_keeps_the_snapshot() {
+        let dir = std::env::temp_dir().join(format!("oracle2vortex_resume_{}", std::process::id()));
+        fs::create_dir_all(&dir).await.unwrap();
+        let export = |resume| TableExport {
+            table: TableRef::parse("ORDERS").unwrap(),
+            output: PartitionOutput::PerPartition,
+            parallel: 1,
+            resume,
+        };
+        let snapshot_of = |snapshot| pipeline(0, WriterConfig::default(), snapshot);
+
+        let first = snapshot_of(Snapshot::Scn(42)).table_snapshot(&export(false), &dir, true).await.unwrap();
+        assert_eq!(first, Some(42));
+        let resumed = snapshot_of(Snapshot::Scn(42)).table_snapshot(&export(true), &dir, true).await.unwrap();
+        assert_eq!(resumed, Some(42));
+
+        // Other snapshot options than the interrupted run
+        assert!(snapshot_of(Snapshot::Scn(43)).table_snapshot(&export(true), &dir, true).await.is_err());
+        assert!(snapshot_of(Snapshot::Off).table_snapshot(&export(true), &dir, true).await.is_err());
+        let at = "2024-03-15T08:00:00".parse().unwrap();
+        assert!(snapshot_of(Snapshot::Timestamp(at)).table_snapshot(&export(true), &dir, true).await.is_err());
+
+        // Without --resume, the saved snapshot is replaced
+        let fresh = snapshot_of(Snapshot::Off).table_snapshot(&export(false), &dir, true).await.unwrap();
+        assert_eq!(fresh, None);
+        assert!(snapshot_of(Snapshot::Scn(42)).table_snapshot(&export(true), &dir, true).await.is_err());
+
+        fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_spool_round_trip() {
+        let file = std::env::temp_dir().join(format!("oracle2vortex_spool_{}.json", std::process::id()));
+        let result = QueryResult {
+            columns: vec![ColumnInfo { name: "ID".to_string(), oracle_type: "NUMBER".to_string() }],
+            records: vec![serde_json::json!({"id": 1}), serde_json::json!({"id": 2})],
+        };
+        Pipeline::write_spool(&file, &result).await.unwrap();
+        let text = fs::read_to_string(&file).await.unwrap();
+        fs::remove_file(&file).await.unwrap();
+
+        let read = JsonStreamReader::parse_spool(&text).unwrap();
+        assert_eq!(read.columns, result.columns);
+        assert_eq!(read.records, result.records);
+
+        // A truncated or padded spool is an error, not fewer rows
+        assert!(JsonStreamReader::parse_spool(&text[..text.len() - 5]).is_err());
+        assert!(JsonStreamReader::parse_spool(&format!("{}\nVersion 23", text)).is_err());
     }
 }
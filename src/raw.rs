@@ -0,0 +1,89 @@
+use std::sync::{Arc, LazyLock};
+use vortex_array::arrays::{FixedSizeListArray, PrimitiveArray};
+use vortex_array::validity::Validity;
+use vortex_array::{ArrayRef, IntoArray};
+use vortex_buffer::Buffer;
+use vortex_dtype::{DType, ExtDType, ExtID, Nullability, PType};
+
+/// Canonical extension type for UUIDs (storage: 16 bytes, as in Arrow)
+pub static UUID_ID: LazyLock<ExtID> = LazyLock::new(|| ExtID::from("arrow.uuid"));
+
+/// Vortex type for RAW(16) columns, which usually hold `SYS_GUID()` values
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Raw16As {
+    /// Fixed-size binary of 16 bytes, like other RAW(n) columns
+    #[default]
+    Binary,
+    /// `arrow.uuid` extension over the 16 bytes
+    Uuid,
+    /// Canonical UUID text (`8-4-4-4-12` lower-case hex)
+    Text,
+}
+
+/// Declared size of a RAW column: `Some(Some(n))` for `RAW(n)`, `Some(None)`
+/// for a bare `RAW`, None for other types (including LONG RAW)
+pub fn raw_size(oracle_type: &str) -> Option<Option<u32>> {
+    let upper = oracle_type.trim().to_uppercase();
+    let rest = upper.strip_prefix("RAW")?.trim();
+    if rest.is_empty() {
+        return Some(None);
+    }
+    let size = rest.strip_prefix('(')?.strip_suffix(')')?.trim().parse().ok()?;
+    Some(Some(size))
+}
+
+/// Vortex dtype for a fixed-size binary column: a list of `size` non-null bytes
+pub fn fixed_binary_dtype(size: u32, nullability: Nullability) -> DType {
+    DType::FixedSizeList(Arc::new(DType::Primitive(PType::U8, Nullability::NonNullable)), size, nullability)
+}
+
+/// Vortex dtype for a UUID column
+pub fn uuid_dtype() -> DType {
+    DType::Extension(Arc::new(ExtDType::new(
+        UUID_ID.clone(),
+        Arc::new(fixed_binary_dtype(16, Nullability::Nullable)),
+        None,
+    )))
+}
+
+/// Canonical text of a 16-byte UUID
+pub fn format_uuid(bytes: &[u8]) -> String {
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+}
+
+/// Build a nullable fixed-size binary array (`mask` marks valid rows)
+pub fn fixed_binary_array(rows: Vec<Vec<u8>>, mask: Vec<bool>, size: u32) -> ArrayRef {
+    let len = rows.len();
+    // Null rows still take `size` bytes
+    let bytes: Buffer<u8> = rows
+        .iter()
+        .flat_map(|row| {
+            let padding = size as usize - row.len().min(size as usize);
+            row.iter().copied().take(size as usize).chain(std::iter::repeat_n(0, padding))
+        })
+        .collect();
+
+    let elements = PrimitiveArray::new(bytes, Validity::NonNullable).into_array();
+    let validity: Validity = mask.into_iter().collect();
+    FixedSizeListArray::new(elements, size, validity, len).into_array()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_types_and_uuids() {
+        assert_eq!(raw_size("RAW(16)"), Some(Some(16)));
+        assert_eq!(raw_size("raw"), Some(None));
+        assert_eq!(raw_size("LONG RAW"), None);
+        assert_eq!(raw_size("VARCHAR2"), None);
+
+        let guid: Vec<u8> = (0u8..16).collect();
+        assert_eq!(format_uuid(&guid), "00010203-0405-0607-0809-0a0b0c0d0e0f");
+
+        let array = fixed_binary_array(vec![guid, Vec::new()], vec![true, false], 16);
+        assert_eq!(array.dtype(), &fixed_binary_dtype(16, Nullability::Nullable));
+    }
+}
@@ -0,0 +1,133 @@
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// A value that could not be converted to its column type
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reject {
+    /// 1-based row in the export
+    pub row: usize,
+    /// Raw value as received from SQLcl (JSON text)
+    pub value: String,
+    pub reason: String,
+}
+
+/// Sink for rejected values: an optional NDJSON file (`--rejects`), an
+/// optional limit (`--max-rejects`) that aborts the export once exceeded, and
+/// `--strict`, which makes the first rejected value an error
+#[derive(Debug, Default)]
+pub struct RejectLog {
+    path: Option<PathBuf>,
+    max_rejects: Option<usize>,
+    strict: bool,
+    state: Mutex<RejectState>,
+}
+
+#[derive(Debug, Default)]
+struct RejectState {
+    file: Option<BufWriter<File>>,
+    count: usize,
+}
+
+impl RejectLog {
+    pub fn new(path: Option<PathBuf>, max_rejects: Option<usize>, strict: bool) -> Self {
+        Self { path, max_rejects, strict, state: Mutex::default() }
+    }
+
+    /// Create (or truncate) the reject file, so that an empty file means no rejects
+    pub fn open(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut state = self.state.lock().unwrap();
+        if state.file.is_none() {
+            let file = File::create(path).with_context(|| format!("Failed to create reject file {:?}", path))?;
+            state.file = Some(BufWriter::new(file));
+        }
+        Ok(())
+    }
+
+    /// Record the rejected values of a column
+    ///
+    /// Fails on the first value in strict mode, or once more than `max_rejects`
+    /// values have been rejected in total.
+    pub fn record(&self, column: &str, target_type: &str, rejects: &[Reject]) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        for reject in rejects {
+            state.count += 1;
+            if let Some(file) = state.file.as_mut() {
+                let line = serde_json::json!({
+                    "row": reject.row,
+                    "column": column,
+                    "value": reject.value,
+                    "target_type": target_type,
+                    "reason": reject.reason,
+                });
+                writeln!(file, "{}", line).context("Failed to write reject file")?;
+            }
+
+            if self.strict {
+                if let Some(file) = state.file.as_mut() {
+                    file.flush().context("Failed to write reject file")?;
+                }
+                anyhow::bail!(
+                    "Column '{}', row {}: {} {} (--strict)",
+                    column, reject.row, reject.value, reject.reason
+                );
+            }
+            if let Some(max) = self.max_rejects.filter(|max| state.count > *max) {
+                if let Some(file) = state.file.as_mut() {
+                    file.flush().context("Failed to write reject file")?;
+                }
+                anyhow::bail!(
+                    "More than {} values rejected (column '{}', row {}: {}), aborting export",
+                    max, column, reject.row, reject.reason
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Number of values rejected so far
+    pub fn count(&self) -> usize {
+        self.state.lock().unwrap().count
+    }
+
+    /// Write buffered lines to the reject file
+    pub fn flush(&self) -> Result<()> {
+        if let Some(file) = self.state.lock().unwrap().file.as_mut() {
+            file.flush().context("Failed to write reject file")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reject_file_and_limit() {
+        let path = std::env::temp_dir().join(format!("oracle2vortex_rejects_{}.ndjson", std::process::id()));
+        let log = RejectLog::new(Some(path.clone()), Some(2), false);
+        log.open().unwrap();
+
+        let reject = |row| Reject { row, value: "\"abc\"".to_string(), reason: "not a number".to_string() };
+        log.record("AMOUNT", "f64", &[reject(3), reject(7)]).unwrap();
+        assert!(log.record("AMOUNT", "f64", &[reject(9)]).is_err());
+        assert_eq!(log.count(), 3);
+
+        let lines = std::fs::read_to_string(&path).unwrap();
+        let first: serde_json::Value = serde_json::from_str(lines.lines().next().unwrap()).unwrap();
+        assert_eq!(first["row"], 3);
+        assert_eq!(first["target_type"], "f64");
+        assert_eq!(lines.lines().count(), 3);
+        std::fs::remove_file(&path).unwrap();
+
+        let strict = RejectLog::new(None, None, true);
+        let error = strict.record("AMOUNT", "f64", &[reject(5)]).unwrap_err();
+        assert_eq!(error.to_string(), "Column 'AMOUNT', row 5: \"abc\" not a number (--strict)");
+    }
+}
@@ -0,0 +1,339 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use vortex_dtype::datetime::{TemporalMetadata, TimeUnit, DATE_ID, TIMESTAMP_ID};
+use vortex_dtype::{DType, DecimalDType, ExtDType, Nullability, PType};
+
+use crate::vector::{self, VectorElement};
+
+/// Target type for a column whose type is forced by the user instead of inferred
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnType {
+    Utf8,
+    I32,
+    I64,
+    F32,
+    F64,
+    Decimal { precision: u8, scale: i8 },
+    Date,
+    Timestamp { unit: TimeUnit, tz: Option<String> },
+    Binary,
+    Bool,
+    Vector { dimension: u32, element: VectorElement },
+}
+
+impl ColumnType {
+    /// Parse a type specification such as `i64`, `decimal(12,2)` or `timestamp(ns, UTC)`
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        let (name, args) = match spec.find('(') {
+            Some(pos) => {
+                let inner = spec[pos + 1..]
+                    .strip_suffix(')')
+                    .with_context(|| format!("Missing closing parenthesis in type '{}'", spec))?;
+                (&spec[..pos], Some(inner))
+            }
+            None => (spec, None),
+        };
+        let args: Vec<&str> = args
+            .map(|a| a.split(',').map(str::trim).collect())
+            .unwrap_or_default();
+
+        let column_type = match (name.trim().to_lowercase().as_str(), args.as_slice()) {
+            ("utf8" | "string" | "varchar", []) => ColumnType::Utf8,
+            ("i32" | "int32", []) => ColumnType::I32,
+            ("i64" | "int64", []) => ColumnType::I64,
+            ("f32" | "float32", []) => ColumnType::F32,
+            ("f64" | "float64" | "double", []) => ColumnType::F64,
+            ("decimal", [precision]) | ("decimal", [precision, _]) => {
+                let precision: u8 = precision
+                    .parse()
+                    .with_context(|| format!("Invalid decimal precision in '{}'", spec))?;
+                let scale: i8 = match args.get(1) {
+                    Some(scale) => scale
+                        .parse()
+                        .with_context(|| format!("Invalid decimal scale in '{}'", spec))?,
+                    None => 0,
+                };
+                if precision == 0 || precision > 38 {
+                    anyhow::bail!("Decimal precision must be between 1 and 38: '{}'", spec);
+                }
+                if scale < 0 || scale as u8 > precision {
+                    anyhow::bail!("Decimal scale must be between 0 and the precision: '{}'", spec);
+                }
+                ColumnType::Decimal { precision, scale }
+            }
+            ("date", []) => ColumnType::Date,
+            ("timestamp", []) => ColumnType::Timestamp { unit: TimeUnit::Microseconds, tz: None },
+            ("timestamp", [unit]) => ColumnType::Timestamp { unit: Self::parse_time_unit(unit)?, tz: None },
+            ("timestamp", [unit, tz]) => ColumnType::Timestamp {
+                unit: Self::parse_time_unit(unit)?,
+                tz: Some(tz.to_string()),
+            },
+            ("binary" | "bytes", []) => ColumnType::Binary,
+            ("bool" | "boolean", []) => ColumnType::Bool,
+            ("vector", [dimension, element]) => ColumnType::Vector {
+                dimension: dimension
+                    .parse()
+                    .with_context(|| format!("Invalid vector dimension in '{}'", spec))?,
+                element: VectorElement::parse(element)
+                    .with_context(|| format!("Unknown vector element type in '{}' (expected f32, f64 or i8)", spec))?,
+            },
+            _ => anyhow::bail!(
+                "Unknown column type '{}' (expected utf8, i32, i64, f32, f64, decimal(p,s), date, \
+                 timestamp(unit, tz), binary, bool or vector(n, f32|f64|i8))",
+                spec
+            ),
+        };
+
+        Ok(column_type)
+    }
+
+    fn parse_time_unit(unit: &str) -> Result<TimeUnit> {
+        match unit.to_lowercase().as_str() {
+            "ns" => Ok(TimeUnit::Nanoseconds),
+            "us" => Ok(TimeUnit::Microseconds),
+            "ms" => Ok(TimeUnit::Milliseconds),
+            "s" => Ok(TimeUnit::Seconds),
+            _ => anyhow::bail!("Unknown time unit '{}' (expected ns, us, ms or s)", unit),
+        }
+    }
+
+    /// Vortex dtype used to store a column of this type
+    pub fn to_dtype(&self, nullability: Nullability) -> DType {
+        match self {
+            ColumnType::Utf8 => DType::Utf8(nullability),
+            ColumnType::I32 => DType::Primitive(PType::I32, nullability),
+            ColumnType::I64 => DType::Primitive(PType::I64, nullability),
+            ColumnType::F32 => DType::Primitive(PType::F32, nullability),
+            ColumnType::F64 => DType::Primitive(PType::F64, nullability),
+            ColumnType::Decimal { precision, scale } => {
+                DType::Decimal(DecimalDType::new(*precision, *scale), nullability)
+            }
+            ColumnType::Date => {
+                let metadata = TemporalMetadata::Date(TimeUnit::Days);
+                let ext_dtype = ExtDType::new(
+                    DATE_ID.clone(),
+                    Arc::new(DType::Primitive(PType::I32, nullability)),
+                    Some(metadata.into()),
+                );
+                DType::Extension(Arc::new(ext_dtype))
+            }
+            ColumnType::Timestamp { unit, tz } => {
+                let metadata = TemporalMetadata::Timestamp(*unit, tz.clone());
+                let ext_dtype = ExtDType::new(
+                    TIMESTAMP_ID.clone(),
+                    Arc::new(DType::Primitive(PType::I64, nullability)),
+                    Some(metadata.into()),
+                );
+                DType::Extension(Arc::new(ext_dtype))
+            }
+            ColumnType::Binary => DType::Binary(nullability),
+            ColumnType::Bool => DType::Bool(nullability),
+            ColumnType::Vector { dimension, element } => vector::vector_dtype(*dimension, *element, nullability),
+        }
+    }
+}
+
+/// A user-supplied type for one column
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnOverride {
+    pub column_type: ColumnType,
+    pub nullable: bool,
+}
+
+impl ColumnOverride {
+    /// Parse a type specification with an optional `not null` suffix
+    /// (e.g. `decimal(12,2) not null`)
+    pub fn parse(spec: &str) -> Result<Self> {
+        let trimmed = spec.trim();
+        let suffix_start = trimmed.len().checked_sub("not null".len()).filter(|i| trimmed.is_char_boundary(*i));
+        let (type_spec, nullable) = match suffix_start.map(|i| trimmed.split_at(i)) {
+            Some((rest, suffix)) if suffix.eq_ignore_ascii_case("not null") => (rest.trim_end(), false),
+            _ => (trimmed, true),
+        };
+
+        Ok(Self {
+            column_type: ColumnType::parse(type_spec)?,
+            nullable,
+        })
+    }
+
+    pub fn dtype(&self) -> DType {
+        let nullability = if self.nullable {
+            Nullability::Nullable
+        } else {
+            Nullability::NonNullable
+        };
+        self.column_type.to_dtype(nullability)
+    }
+}
+
+/// Column type overrides from `--schema-file` and `--cast`
+///
+/// Column names are matched case-insensitively since SQLcl lowercases
+/// the keys of JSON records while Oracle reports names in upper case.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaOverrides {
+    columns: HashMap<String, ColumnOverride>,
+}
+
+impl SchemaOverrides {
+    /// Build overrides from an optional schema file and `COL=type` cast arguments.
+    /// Casts given on the command line take precedence over the schema file.
+    pub fn load(schema_file: Option<&Path>, casts: &[String]) -> Result<Self> {
+        let mut overrides = match schema_file {
+            Some(path) => Self::from_file(path)?,
+            None => Self::default(),
+        };
+
+        for cast in casts {
+            let (column, spec) = cast
+                .split_once('=')
+                .with_context(|| format!("Invalid --cast '{}' (expected COLUMN=type)", cast))?;
+            overrides.insert(column, ColumnOverride::parse(spec)?);
+        }
+
+        Ok(overrides)
+    }
+
+    /// Load overrides from a JSON or TOML schema file
+    ///
+    /// JSON files map column names to type specifications, either at the top
+    /// level or under a `columns` key. TOML files use a flat `COLUMN = "type"`
+    /// layout, optionally under a `[columns]` table.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read schema file {:?}", path))?;
+
+        let is_toml = path
+            .extension()
+            .map(|ext| ext.eq_ignore_ascii_case("toml"))
+            .unwrap_or(false);
+
+        let entries = if is_toml {
+            Self::parse_toml(&content)?
+        } else {
+            Self::parse_json(&content)?
+        };
+
+        let mut overrides = Self::default();
+        for (column, spec) in entries {
+            let column_override = ColumnOverride::parse(&spec)
+                .with_context(|| format!("Invalid type for column '{}' in {:?}", column, path))?;
+            overrides.insert(&column, column_override);
+        }
+
+        tracing::info!("Loaded {} column type overrides from {:?}", overrides.len(), path);
+
+        Ok(overrides)
+    }
+
+    fn parse_json(content: &str) -> Result<Vec<(String, String)>> {
+        let parsed: Value = serde_json::from_str(content).context("Schema file is not valid JSON")?;
+        let columns = parsed
+            .get("columns")
+            .unwrap_or(&parsed)
+            .as_object()
+            .context("Schema file must contain an object mapping columns to types")?;
+
+        columns
+            .iter()
+            .map(|(column, spec)| match spec {
+                Value::String(s) => Ok((column.clone(), s.clone())),
+                _ => anyhow::bail!("Type for column '{}' must be a string", column),
+            })
+            .collect()
+    }
+
+    fn parse_toml(content: &str) -> Result<Vec<(String, String)>> {
+        let parsed: toml::Table = toml::from_str(content).context("Schema file is not valid TOML")?;
+        let columns = match parsed.get("columns") {
+            Some(toml::Value::Table(columns)) => columns,
+            Some(_) => anyhow::bail!("'columns' in the schema file must be a table"),
+            None => &parsed,
+        };
+
+        columns
+            .iter()
+            .map(|(column, spec)| match spec {
+                toml::Value::String(s) => Ok((column.clone(), s.clone())),
+                _ => anyhow::bail!("Type for column '{}' must be a string", column),
+            })
+            .collect()
+    }
+
+    fn insert(&mut self, column: &str, column_override: ColumnOverride) {
+        self.columns.insert(column.trim().to_lowercase(), column_override);
+    }
+
+    /// Look up the override for a column, if any
+    pub fn get(&self, column: &str) -> Option<&ColumnOverride> {
+        self.columns.get(&column.to_lowercase())
+    }
+
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_column_types() {
+        assert_eq!(ColumnType::parse("utf8").unwrap(), ColumnType::Utf8);
+        assert_eq!(ColumnType::parse("I64").unwrap(), ColumnType::I64);
+        assert_eq!(
+            ColumnType::parse("decimal(12, 2)").unwrap(),
+            ColumnType::Decimal { precision: 12, scale: 2 }
+        );
+        assert_eq!(
+            ColumnType::parse("timestamp(ns, UTC)").unwrap(),
+            ColumnType::Timestamp { unit: TimeUnit::Nanoseconds, tz: Some("UTC".to_string()) }
+        );
+        assert_eq!(
+            ColumnType::parse("vector(768, f32)").unwrap(),
+            ColumnType::Vector { dimension: 768, element: VectorElement::F32 }
+        );
+        assert!(ColumnType::parse("decimal(40,2)").is_err());
+        assert!(ColumnType::parse("timestamp(days)").is_err());
+        assert!(ColumnType::parse("varchar2").is_err());
+    }
+
+    #[test]
+    fn test_parse_column_override_nullability() {
+        let o = ColumnOverride::parse("i64 NOT NULL").unwrap();
+        assert_eq!(o.column_type, ColumnType::I64);
+        assert!(!o.nullable);
+        assert!(ColumnOverride::parse("utf8").unwrap().nullable);
+        assert!(ColumnOverride::parse("İ").is_err());
+        assert!(!ColumnOverride::parse("utf8 Not Null").unwrap().nullable);
+    }
+
+    #[test]
+    fn test_schema_sources() {
+        let json = SchemaOverrides::parse_json(r#"{"columns": {"ZIP": "utf8"}}"#).unwrap();
+        assert_eq!(json, vec![("ZIP".to_string(), "utf8".to_string())]);
+
+        let toml = SchemaOverrides::parse_toml("# overrides\n[columns]\nZIP = \"utf8\"\n").unwrap();
+        assert_eq!(toml, vec![("ZIP".to_string(), "utf8".to_string())]);
+        let toml = SchemaOverrides::parse_toml(
+            "[columns]\n\"A=B\" = 'decimal(10,2)'  # comment\n'Mixed Case' = \"utf8 not null\"\n",
+        )
+        .unwrap();
+        assert_eq!(toml[0], ("A=B".to_string(), "decimal(10,2)".to_string()));
+        assert_eq!(toml[1].0, "Mixed Case");
+        assert!(SchemaOverrides::parse_toml("[columns]\nZIP = 5\n").is_err());
+
+        let overrides = SchemaOverrides::load(None, &["Zip=binary".to_string()]).unwrap();
+        assert_eq!(overrides.get("ZIP").unwrap().column_type, ColumnType::Binary);
+    }
+}
@@ -0,0 +1,199 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Session settings the JSON parser depends on, set by `SqlclProcess::spawn`
+///
+/// NLS_TERRITORY and NLS_LANGUAGE are included because changing them resets
+/// the numeric characters and date formats derived from them.
+const PROTECTED_NLS: &[&str] = &[
+    "NLS_NUMERIC_CHARACTERS",
+    "NLS_DATE_FORMAT",
+    "NLS_TIMESTAMP_FORMAT",
+    "NLS_TIMESTAMP_TZ_FORMAT",
+    "NLS_TERRITORY",
+    "NLS_LANGUAGE",
+];
+
+/// SQLcl options that shape the output stream (or have their own CLI option)
+const PROTECTED_SET: &[&str] = &[
+    "SQLFORMAT", "FEEDBACK", "TIMING", "VERIFY", "HEADING", "HEA", "PAGESIZE", "PAGES", "TERMOUT", "TERM",
+    "TRIMSPOOL", "ENCODING", "LONG", "LONGCHUNKSIZE", "LOBPREFETCH", "ECHO", "SERVEROUTPUT", "AUTOPRINT",
+    "DRIVER",
+];
+
+/// Statements from `--session-init`, run in every SQLcl session after the
+/// mandatory settings and before the query
+#[derive(Debug, Clone, Default)]
+pub struct SessionInit {
+    pub statements: Vec<String>,
+}
+
+impl SessionInit {
+    /// Read a session init file, dropping (with a warning) statements that
+    /// would change a setting the export depends on
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read session init file {:?}", path))?;
+
+        let mut statements = Vec::new();
+        for statement in split_statements(&text) {
+            match protected_setting(&statement) {
+                Some(setting) => tracing::warn!(
+                    "Session init: skipping '{}', which changes {} (required by the export)",
+                    statement, setting
+                ),
+                None => statements.push(statement),
+            }
+        }
+
+        tracing::info!("Session init: {} statements from {:?}", statements.len(), path);
+        Ok(Self { statements })
+    }
+
+    /// The statements as SQLcl input, each with its terminator
+    pub fn script(&self) -> String {
+        let mut script = String::new();
+        for statement in &self.statements {
+            script.push_str(statement);
+            if is_plsql_block(statement) {
+                script.push_str("\n/");
+            } else if !is_sqlcl_command(statement) {
+                script.push(';');
+            }
+            script.push('\n');
+        }
+        script
+    }
+}
+
+/// Split a script into statements
+///
+/// SQL statements end with `;` (or a `/` line, for PL/SQL blocks); SQLcl
+/// commands such as `SET` take a single line. Blank lines and `--` comments
+/// between statements are ignored.
+fn split_statements(text: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if current.is_empty() {
+            if trimmed.is_empty() || trimmed.starts_with("--") {
+                continue;
+            }
+            if is_sqlcl_command(trimmed) {
+                statements.push(trimmed.trim_end_matches(';').to_string());
+                continue;
+            }
+        }
+
+        if trimmed == "/" {
+            statements.push(std::mem::take(&mut current).trim().to_string());
+            continue;
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(line);
+        if trimmed.ends_with(';') && !is_plsql_block(&current) {
+            let statement = std::mem::take(&mut current);
+            statements.push(statement.trim().trim_end_matches(';').trim_end().to_string());
+        }
+    }
+
+    if !current.trim().is_empty() {
+        statements.push(current.trim().trim_end_matches(';').trim_end().to_string());
+    }
+    statements.retain(|s| !s.is_empty());
+    statements
+}
+
+fn first_word(statement: &str) -> String {
+    statement.split_whitespace().next().unwrap_or("").to_uppercase()
+}
+
+/// SQLcl commands (as opposed to SQL) that the script may contain; each takes
+/// a single line
+fn is_sqlcl_command(statement: &str) -> bool {
+    matches!(
+        first_word(statement).as_str(),
+        "SET" | "DEFINE" | "UNDEFINE" | "PROMPT" | "SPOOL" | "EXEC" | "EXECUTE"
+    )
+}
+
+/// Blocks end with a `/` line rather than at the first `;`
+fn is_plsql_block(statement: &str) -> bool {
+    let upper = statement.trim_start().to_uppercase();
+    matches!(first_word(&upper).as_str(), "BEGIN" | "DECLARE")
+        || upper.starts_with("CREATE OR REPLACE")
+        || upper.split_whitespace().take(3).any(|w| matches!(w, "PROCEDURE" | "FUNCTION" | "PACKAGE" | "TRIGGER"))
+}
+
+/// Name of the protected setting a statement changes, if any
+fn protected_setting(statement: &str) -> Option<String> {
+    let upper = statement.to_uppercase();
+    let words: Vec<&str> = upper.split_whitespace().collect();
+
+    match words.as_slice() {
+        ["SET", option, ..] => PROTECTED_SET
+            .iter()
+            .find(|p| **p == *option)
+            .map(|p| format!("SET {}", p)),
+        // Output would land in the JSON stream
+        [first, ..] if matches!(*first, "SELECT" | "WITH" | "PROMPT" | "SPOOL" | "PRINT") => {
+            Some("the output stream".to_string())
+        }
+        // ALTER SESSION, also inside EXECUTE IMMEDIATE, or DBMS_SESSION.SET_NLS
+        _ if upper.contains("ALTER SESSION") || upper.contains("SET_NLS") => PROTECTED_NLS
+            .iter()
+            .find(|nls| upper.split(|c: char| !c.is_ascii_alphanumeric() && c != '_').any(|w| w == **nls))
+            .map(|nls| nls.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_init_statements() {
+        let script = "\
+-- schema and sort order
+ALTER SESSION SET CURRENT_SCHEMA = SALES;
+ALTER SESSION SET NLS_SORT = BINARY_CI
+  NLS_COMP = LINGUISTIC;
+SET SQLFORMAT CSV
+alter session set nls_date_format = 'DD/MM/YYYY';
+BEGIN
+  DBMS_SESSION.SET_IDENTIFIER('export');
+END;
+/
+SELECT 1 FROM dual;
+EXEC DBMS_SESSION.SET_NLS('NLS_NUMERIC_CHARACTERS', ''',.''')
+exec dbms_session.set_nls('nls_sort', 'BINARY')
+";
+        let statements = split_statements(script);
+        assert_eq!(statements.len(), 8);
+        assert_eq!(statements[1], "ALTER SESSION SET NLS_SORT = BINARY_CI\n  NLS_COMP = LINGUISTIC");
+        assert!(statements[4].starts_with("BEGIN") && statements[4].ends_with("END;"));
+
+        let protected: Vec<Option<String>> = statements.iter().map(|s| protected_setting(s)).collect();
+        assert_eq!(protected[0], None);
+        assert_eq!(protected[1], None);
+        assert_eq!(protected[2].as_deref(), Some("SET SQLFORMAT"));
+        assert_eq!(protected[3].as_deref(), Some("NLS_DATE_FORMAT"));
+        assert_eq!(protected[4], None);
+        assert_eq!(protected[5].as_deref(), Some("the output stream"));
+        assert_eq!(protected[6].as_deref(), Some("NLS_NUMERIC_CHARACTERS"));
+        assert_eq!(protected[7], None);
+
+        let init = SessionInit { statements: vec![statements[0].clone(), statements[4].clone(), statements[7].clone()] };
+        assert_eq!(
+            init.script(),
+            "ALTER SESSION SET CURRENT_SCHEMA = SALES;\nBEGIN\n  DBMS_SESSION.SET_IDENTIFIER('export');\nEND;\n/\n\
+             exec dbms_session.set_nls('nls_sort', 'BINARY')\n"
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use anyhow::{Context, Result};
+use jiff::civil::DateTime;
+use serde_json::Value;
+use std::fmt;
+
+/// Point in time the export reads, so that every query of a run sees the same data
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Snapshot {
+    /// Capture the current SCN when the export runs several queries (auto-batching)
+    #[default]
+    Auto,
+    /// Each query reads the data as of its own start
+    Off,
+    /// A given system change number (`--as-of-scn`)
+    Scn(u64),
+    /// A given time (`--as-of-timestamp`), converted to an SCN once
+    Timestamp(DateTime),
+}
+
+impl Snapshot {
+    /// Build the snapshot from CLI options
+    ///
+    /// # Arguments
+    /// * `scn` - `--as-of-scn`
+    /// * `timestamp` - `--as-of-timestamp`, ISO 8601 without zone (database time)
+    /// * `disabled` - `--no-snapshot`
+    pub fn from_args(scn: Option<u64>, timestamp: Option<&str>, disabled: bool) -> Result<Self> {
+        Ok(match (scn, timestamp) {
+            (Some(_), Some(_)) => anyhow::bail!("--as-of-scn and --as-of-timestamp are mutually exclusive"),
+            (Some(scn), None) => Snapshot::Scn(scn),
+            (None, Some(timestamp)) => Snapshot::Timestamp(parse_timestamp(timestamp)?),
+            (None, None) if disabled => Snapshot::Off,
+            (None, None) => Snapshot::Auto,
+        })
+    }
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Snapshot::Auto => write!(f, "auto"),
+            Snapshot::Off => write!(f, "off"),
+            Snapshot::Scn(scn) => write!(f, "scn {}", scn),
+            Snapshot::Timestamp(at) => write!(f, "timestamp {}", at),
+        }
+    }
+}
+
+/// Snapshot option and pinned SCN of a `--table` export, saved so that a
+/// resumed run reads the same data as the units already exported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedSnapshot {
+    /// The snapshot option of the run (`Snapshot` display form)
+    pub option: String,
+    pub scn: Option<u64>,
+}
+
+impl SavedSnapshot {
+    pub fn to_text(&self) -> String {
+        let scn = self.scn.map(|scn| scn.to_string()).unwrap_or_else(|| "none".to_string());
+        format!("option={}\nscn={}\n", self.option, scn)
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut option = None;
+        let mut scn = None;
+        for line in text.lines() {
+            match line.split_once('=') {
+                Some(("option", value)) => option = Some(value.to_string()),
+                Some(("scn", "none")) => scn = Some(None),
+                Some(("scn", value)) => {
+                    scn = Some(Some(value.parse().with_context(|| format!("Invalid SCN '{}'", value))?))
+                }
+                _ => {}
+            }
+        }
+        Ok(Self {
+            option: option.context("No snapshot option")?,
+            scn: scn.context("No SCN")?,
+        })
+    }
+}
+
+/// Parse `YYYY-MM-DD HH:MI:SS[.FF]` (or with a `T`)
+fn parse_timestamp(s: &str) -> Result<DateTime> {
+    s.trim()
+        .replacen(' ', "T", 1)
+        .parse()
+        .with_context(|| format!("Invalid --as-of-timestamp '{}' (expected YYYY-MM-DD HH:MI:SS)", s))
+}
+
+/// Queries returning the current SCN as `SCN`, tried in order: V$DATABASE needs
+/// SELECT_CATALOG_ROLE, DBMS_FLASHBACK needs EXECUTE on the package
+pub const CURRENT_SCN_QUERIES: &[&str] = &[
+    "SELECT CURRENT_SCN AS SCN FROM V$DATABASE",
+    "SELECT DBMS_FLASHBACK.GET_SYSTEM_CHANGE_NUMBER AS SCN FROM DUAL",
+];
+
+/// Query returning the SCN of a point in time as `SCN`
+pub fn timestamp_scn_query(timestamp: &DateTime) -> String {
+    format!(
+        "SELECT TIMESTAMP_TO_SCN(TIMESTAMP '{}') AS SCN FROM DUAL",
+        timestamp.strftime("%Y-%m-%d %H:%M:%S%.f")
+    )
+}
+
+/// SQLcl commands putting the session in flashback query mode at `scn`
+///
+/// If the call fails (no EXECUTE on DBMS_FLASHBACK, SCN older than the undo
+/// retention), SQLcl exits with a failure status instead of running the query
+/// on current data.
+pub fn enable_script(scn: u64) -> String {
+    format!(
+        "WHENEVER SQLERROR EXIT FAILURE\nEXEC DBMS_FLASHBACK.ENABLE_AT_SYSTEM_CHANGE_NUMBER({})\nWHENEVER SQLERROR CONTINUE\n",
+        scn
+    )
+}
+
+/// SCN from the rows of one of the queries above
+pub fn scn_from_rows(rows: &[Value]) -> Option<u64> {
+    match rows.first()?.get("scn")? {
+        Value::Number(n) => n.as_u64().or_else(|| n.as_f64().filter(|f| *f >= 0.0).map(|f| f as u64)),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_snapshot_options() {
+        assert_eq!(Snapshot::from_args(None, None, false).unwrap(), Snapshot::Auto);
+        assert_eq!(Snapshot::from_args(None, None, true).unwrap(), Snapshot::Off);
+        assert_eq!(Snapshot::from_args(Some(42), None, true).unwrap(), Snapshot::Scn(42));
+        assert!(Snapshot::from_args(Some(42), Some("2024-03-15 08:00:00"), false).is_err());
+        assert!(Snapshot::from_args(None, Some("yesterday"), false).is_err());
+
+        let Snapshot::Timestamp(at) = Snapshot::from_args(None, Some("2024-03-15 08:00:00.5"), false).unwrap() else {
+            panic!("expected a timestamp");
+        };
+        assert_eq!(
+            timestamp_scn_query(&at),
+            "SELECT TIMESTAMP_TO_SCN(TIMESTAMP '2024-03-15 08:00:00.5') AS SCN FROM DUAL"
+        );
+        assert_eq!(scn_from_rows(&[json!({"scn": 1234567})]), Some(1234567));
+        assert_eq!(scn_from_rows(&[json!({"scn": "89"})]), Some(89));
+        assert_eq!(scn_from_rows(&[]), None);
+        let saved = SavedSnapshot { option: Snapshot::Timestamp(at).to_string(), scn: Some(7) };
+        assert_eq!(saved.option, "timestamp 2024-03-15T08:00:00.5");
+        assert_eq!(SavedSnapshot::parse(&saved.to_text()).unwrap(), saved);
+        let off = SavedSnapshot { option: Snapshot::Off.to_string(), scn: None };
+        assert_eq!(SavedSnapshot::parse(&off.to_text()).unwrap(), off);
+        assert!(SavedSnapshot::parse("option=auto\n").is_err());
+        assert!(enable_script(42).starts_with("WHENEVER SQLERROR EXIT FAILURE\nEXEC DBMS_FLASHBACK.ENABLE_AT_SYSTEM_CHANGE_NUMBER(42)\n"));
+    }
+}
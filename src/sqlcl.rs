@@ -1,11 +1,46 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use tokio::io::AsyncWriteExt;
 use tokio::process::Command as TokioCommand;
 
+use crate::json_stream::{JsonStreamReader, QueryResult};
+use crate::session::SessionInit;
+use crate::snapshot;
+
+#[derive(Clone)]
 pub struct SqlclConfig {
     pub connection_string: String,  // Either full connect string or built from components
     pub sqlcl_path: String,
     pub thick: bool,
+    pub lob_fetch: LobFetchSettings,
+    /// User statements run after the mandatory settings (`--session-init`)
+    pub session_init: SessionInit,
+    /// Read the data as of this SCN (flashback query mode for the whole session;
+    /// the session fails if flashback query mode cannot be enabled)
+    pub as_of_scn: Option<u64>,
+}
+
+/// SQLcl settings that control how much of each LOB is fetched
+///
+/// SQLcl cuts CLOB/LONG values at `SET LONG` bytes, so the default is raised
+/// well above SQLcl's own (80) to get values in full.
+#[derive(Debug, Clone)]
+pub struct LobFetchSettings {
+    /// `SET LONG`: maximum bytes displayed for CLOB/LONG values
+    pub long: u64,
+    /// `SET LONGCHUNKSIZE`: bytes fetched per round trip for CLOB/LONG values
+    pub long_chunk_size: u64,
+    /// `SET LOBPREFETCH`: bytes prefetched with each row (SQLcl default if unset)
+    pub lob_prefetch: Option<u64>,
+}
+
+impl Default for LobFetchSettings {
+    fn default() -> Self {
+        Self {
+            long: 2_000_000_000,
+            long_chunk_size: 2_000_000_000,
+            lob_prefetch: None,
+        }
+    }
 }
 
 pub struct SqlclProcess {
@@ -45,11 +80,29 @@ impl SqlclProcess {
             stdin.write_all(b"SET TRIMSPOOL ON\n").await?;      // Supprime les espaces en fin de ligne
             stdin.write_all(b"SET ENCODING UTF-8\n").await?;    // Force l'UTF-8
 
+            // Taille des LOB récupérés (évite la troncature des CLOB)
+            let lob = &config.lob_fetch;
+            stdin.write_all(format!("SET LONG {}\n", lob.long).as_bytes()).await?;
+            stdin.write_all(format!("SET LONGCHUNKSIZE {}\n", lob.long_chunk_size).as_bytes()).await?;
+            if let Some(prefetch) = lob.lob_prefetch {
+                stdin.write_all(format!("SET LOBPREFETCH {}\n", prefetch).as_bytes()).await?;
+            }
+
             // Configuration Régionale (Session) pour compatibilité JSON stricte
             stdin.write_all(b"ALTER SESSION SET NLS_NUMERIC_CHARACTERS = '.,';\n").await?;
             stdin.write_all(b"ALTER SESSION SET NLS_DATE_FORMAT = 'YYYY-MM-DD\"T\"HH24:MI:SS';\n").await?;
             stdin.write_all(b"ALTER SESSION SET NLS_TIMESTAMP_FORMAT = 'YYYY-MM-DD\"T\"HH24:MI:SS.FF';\n").await?;
-            stdin.write_all(b"ALTER SESSION SET NLS_TIMESTAMP_TZ_FORMAT = 'YYYY-MM-DD\"T\"HH24:MI:SS.FF TZH:TZM';\n").await?;
+            stdin.write_all(b"ALTER SESSION SET NLS_TIMESTAMP_TZ_FORMAT = 'YYYY-MM-DD\"T\"HH24:MI:SS.FF TZR';\n").await?;
+
+            // Réglages utilisateur (--session-init), sans effet sur les formats ci-dessus
+            if !config.session_init.statements.is_empty() {
+                stdin.write_all(config.session_init.script().as_bytes()).await?;
+            }
+
+            // Lecture cohérente : toutes les requêtes de l'export voient le même SCN
+            if let Some(scn) = config.as_of_scn {
+                stdin.write_all(snapshot::enable_script(scn).as_bytes()).await?;
+            }
 
             // Configuration du format de sortie SQLcl
             stdin.write_all(b"SET SQLFORMAT JSON\n").await?;
@@ -75,7 +128,25 @@ impl SqlclProcess {
         self.child.stdout.take()
     }
 
+    /// Wait for SQLcl to exit; a failure exit status (e.g. from `WHENEVER
+    /// SQLERROR EXIT FAILURE`) is an error
     pub async fn wait(&mut self) -> Result<std::process::ExitStatus> {
-        Ok(self.child.wait().await?)
+        let status = self.child.wait().await?;
+        if !status.success() {
+            anyhow::bail!("SQLcl exited with {}", status);
+        }
+        Ok(status)
+    }
+
+    /// Read the whole JSON output and wait for SQLcl to exit
+    ///
+    /// The exit status is checked before the output is used: a session that
+    /// stopped early leaves no JSON to parse, and the exit status says why.
+    pub async fn read_json(&mut self) -> Result<QueryResult> {
+        let stdout = self.stdout()
+            .context("Failed to get SQLcl stdout")?;
+        let result = JsonStreamReader::new(stdout).read_all_json().await;
+        self.wait().await?;
+        result
     }
 }
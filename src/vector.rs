@@ -0,0 +1,166 @@
+use serde_json::Value;
+use std::sync::Arc;
+use vortex_array::arrays::{FixedSizeListArray, PrimitiveArray};
+use vortex_array::validity::Validity;
+use vortex_array::{ArrayRef, IntoArray};
+use vortex_buffer::Buffer;
+use vortex_dtype::{DType, Nullability, PType};
+
+/// Element type of an Oracle 23ai VECTOR column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VectorElement {
+    F32,
+    F64,
+    I8,
+}
+
+impl VectorElement {
+    /// Parse an element type, by Oracle name (`FLOAT32`, `FLOAT64`, `INT8`) or
+    /// short name (`f32`, `f64`, `i8`)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "float32" | "f32" => Some(VectorElement::F32),
+            "float64" | "f64" => Some(VectorElement::F64),
+            "int8" | "i8" => Some(VectorElement::I8),
+            _ => None,
+        }
+    }
+
+    pub fn ptype(self) -> PType {
+        match self {
+            VectorElement::F32 => PType::F32,
+            VectorElement::F64 => PType::F64,
+            VectorElement::I8 => PType::I8,
+        }
+    }
+
+    /// Whether every component can be stored without changing its value class
+    /// (INT8 components must be integers in -128..=127)
+    fn accepts(self, components: &[f64]) -> bool {
+        match self {
+            VectorElement::F32 | VectorElement::F64 => components.iter().all(|c| c.is_finite()),
+            VectorElement::I8 => components
+                .iter()
+                .all(|c| c.fract() == 0.0 && (i8::MIN as f64..=i8::MAX as f64).contains(c)),
+        }
+    }
+}
+
+/// Vortex dtype for a vector column: a fixed-size list of non-null components
+pub fn vector_dtype(dimension: u32, element: VectorElement, nullability: Nullability) -> DType {
+    DType::FixedSizeList(
+        Arc::new(DType::Primitive(element.ptype(), Nullability::NonNullable)),
+        dimension,
+        nullability,
+    )
+}
+
+/// Dimension and element type declared by an Oracle type such as `VECTOR(768, FLOAT32)`
+///
+/// A flexible dimension (`*`) gives None. A flexible or unknown format (SQLcl
+/// reports the type as a bare `VECTOR`) is read as FLOAT64, which holds FLOAT32,
+/// FLOAT64 and INT8 components exactly. Returns None for other types, including
+/// BINARY vectors (bit-packed, not supported).
+pub fn parse_oracle_type(oracle_type: &str) -> Option<(Option<u32>, VectorElement)> {
+    let upper = oracle_type.trim().to_uppercase();
+    let rest = upper.strip_prefix("VECTOR")?.trim();
+    if rest.is_empty() {
+        return Some((None, VectorElement::F64));
+    }
+
+    let args = rest.strip_prefix('(')?.strip_suffix(')')?;
+    let mut parts = args.split(',').map(str::trim);
+    let dimension = match parts.next()? {
+        "*" => None,
+        n => Some(n.parse().ok()?),
+    };
+    let element = match parts.next() {
+        None | Some("*") => VectorElement::F64,
+        Some(format) => VectorElement::parse(format)?,
+    };
+
+    Some((dimension, element))
+}
+
+/// Components of a vector value: a JSON array of numbers or its text form (`"[1.5,2]"`)
+pub fn parse_components(value: &Value) -> Option<Vec<f64>> {
+    let parsed;
+    let array = match value {
+        Value::Array(items) => items,
+        Value::String(s) => {
+            parsed = serde_json::from_str::<Value>(s).ok()?;
+            parsed.as_array()?
+        }
+        _ => return None,
+    };
+    array.iter().map(Value::as_f64).collect()
+}
+
+/// Check a vector value against the column's dimension and element type
+pub fn convert(value: &Value, dimension: u32, element: VectorElement) -> Option<Vec<f64>> {
+    parse_components(value).filter(|c| c.len() == dimension as usize && element.accepts(c))
+}
+
+/// Build a fixed-size list array from converted rows (`mask` marks valid rows)
+pub fn vector_array(
+    rows: Vec<Vec<f64>>,
+    mask: Vec<bool>,
+    dimension: u32,
+    element: VectorElement,
+    nullable: bool,
+) -> ArrayRef {
+    let len = rows.len();
+    // Null rows still take `dimension` slots in the element array
+    let components = rows.iter().flat_map(|row| {
+        let padding = dimension as usize - row.len().min(dimension as usize);
+        row.iter().copied().chain(std::iter::repeat_n(0.0, padding))
+    });
+
+    let elements = match element {
+        VectorElement::F32 => {
+            let buffer: Buffer<f32> = components.map(|c| c as f32).collect();
+            PrimitiveArray::new(buffer, Validity::NonNullable).into_array()
+        }
+        VectorElement::F64 => {
+            let buffer: Buffer<f64> = components.collect();
+            PrimitiveArray::new(buffer, Validity::NonNullable).into_array()
+        }
+        VectorElement::I8 => {
+            let buffer: Buffer<i8> = components.map(|c| c as i8).collect();
+            PrimitiveArray::new(buffer, Validity::NonNullable).into_array()
+        }
+    };
+
+    let validity = if nullable {
+        mask.into_iter().collect()
+    } else {
+        Validity::NonNullable
+    };
+    FixedSizeListArray::new(elements, dimension, validity, len).into_array()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_oracle_vector_types() {
+        assert_eq!(parse_oracle_type("VECTOR(768, FLOAT32)"), Some((Some(768), VectorElement::F32)));
+        assert_eq!(parse_oracle_type("vector(3,int8)"), Some((Some(3), VectorElement::I8)));
+        assert_eq!(parse_oracle_type("VECTOR(*, FLOAT64)"), Some((None, VectorElement::F64)));
+        assert_eq!(parse_oracle_type("VECTOR"), Some((None, VectorElement::F64)));
+        assert_eq!(parse_oracle_type("VECTOR(3, *)"), Some((Some(3), VectorElement::F64)));
+        assert_eq!(parse_oracle_type("VECTOR(1024, BINARY)"), None);
+        assert_eq!(parse_oracle_type("VARCHAR2"), None);
+    }
+
+    #[test]
+    fn test_convert_components() {
+        let text = serde_json::json!("[1.0E+000,-2.5E+000,3]");
+        assert_eq!(convert(&text, 3, VectorElement::F32), Some(vec![1.0, -2.5, 3.0]));
+        // Wrong dimension, non-integer INT8 component
+        assert_eq!(convert(&text, 4, VectorElement::F32), None);
+        assert_eq!(convert(&text, 3, VectorElement::I8), None);
+        assert_eq!(convert(&serde_json::json!([1, 2, 300]), 3, VectorElement::I8), None);
+    }
+}
@@ -1,37 +1,280 @@
 use anyhow::{Context, Result};
 use serde_json::Value;
-use std::path::Path;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use vortex_array::arrays::{BoolArray, PrimitiveArray, StructArray, VarBinArray};
+use vortex_array::arrays::{BoolArray, DecimalArray, ExtensionArray, PrimitiveArray, StructArray, VarBinArray};
 use vortex_array::validity::Validity;
-use vortex_array::IntoArray;
+use vortex_array::{ArrayRef, IntoArray};
 use vortex_buffer::Buffer;
-use vortex_dtype::{DType, ExtDType, Nullability, PType};
+use vortex_dtype::{DType, DecimalDType, ExtDType, FieldNames, Nullability, PType};
 use vortex_dtype::datetime::{TemporalMetadata, TimeUnit, DATE_ID, TIMESTAMP_ID};
 use vortex_file::WriteOptionsSessionExt;
 use vortex_io::session::RuntimeSession;
 use vortex_session::VortexSession;
 use jiff::civil::{Date, DateTime};
+use jiff::tz::TimeZone;
+use sha2::{Digest, Sha256};
+
+use crate::columns::ColumnLayout;
+use crate::detection::{DetectionPolicy, Heuristics, OracleDateAs};
+use crate::flags::{BoolFlags, FlagRule};
+use crate::geometry::{self, SdoGeometry};
+use crate::interval::{self, IntervalKind, MonthDayNano, INTERVAL_ID};
+use crate::json_stream::ColumnInfo;
+use crate::lobs::{self, LobMode, LobPolicy};
+use crate::schema::{ColumnOverride, ColumnType, SchemaOverrides};
+use crate::nested::{self, Shape};
+use crate::object_types::{self, TypeCatalog};
+use crate::raw::{self, Raw16As};
+use crate::rejects::{Reject, RejectLog};
+use crate::vector::{self, VectorElement};
+
+/// Options controlling how records are converted into Vortex columns
+#[derive(Debug, Clone, Default)]
+pub struct WriterConfig {
+    /// Handling of LOB columns (CLOB, BLOB, etc.): keep, skip, truncate or externalise
+    pub lobs: LobPolicy,
+    /// User-supplied column types that replace inference
+    pub schema: SchemaOverrides,
+    /// Type-detection heuristics and per-column exemptions
+    pub detection: DetectionPolicy,
+    /// Output column order and renames
+    pub layout: ColumnLayout,
+    /// What to do when the columns change between auto-batches
+    pub schema_drift: SchemaDrift,
+    /// Compare LOB values with the `<COL>__LOB_LENGTH` columns added to the query
+    /// and drop those columns from the output
+    pub verify_lob_lengths: bool,
+    /// Add a `<column>_tz` column with the original zone of each timestamp value
+    pub time_zone_columns: bool,
+    /// Vortex type for Oracle DATE columns
+    pub oracle_date_as: OracleDateAs,
+    /// Write JSON documents as nested structs and lists
+    pub nested_json: bool,
+    /// Flag columns (`Y`/`N`, ...) to convert to Bool
+    pub bool_flags: BoolFlags,
+    /// Keep every inferred integer column I64 and every float column F64
+    pub wide_numbers: bool,
+    /// Narrow integer columns without a declared precision to the range of
+    /// their values (the type then depends on the data of each run)
+    pub narrow_by_values: bool,
+    /// Vortex type for RAW(16) columns
+    pub raw16_as: Raw16As,
+    /// NDJSON file receiving every value that could not be converted
+    pub rejects_path: Option<PathBuf>,
+    /// Abort the export once more values than this have been rejected
+    pub max_rejects: Option<usize>,
+    /// Fail instead of storing a value that would be changed or lost (null on
+    /// conversion failure, truncated LOB, dropped precision or time zone)
+    pub strict: bool,
+}
+
+/// Reaction to columns appearing or disappearing between auto-batches
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum SchemaDrift {
+    /// Log a warning and keep the union of all columns
+    #[default]
+    Warn,
+    /// Abort the export
+    Error,
+}
 
 pub struct VortexWriter {
+    /// Union of the record keys seen so far, in order of first appearance
     field_order: Vec<String>,
+    known_fields: HashSet<String>,
+    columns: Vec<ColumnInfo>,
+    /// Definitions of the object types and collections used by the columns
+    types: TypeCatalog,
+    /// Values rejected during conversion
+    rejects: RejectLog,
+    batch: usize,
     records: Vec<Value>,
-    skip_lobs: bool,
+    config: WriterConfig,
+}
+
+/// Tracks values of one column that could not be converted to its target type
+struct ConversionFailures<'a> {
+    column: &'a str,
+    target: String,
+    rejected: Vec<Reject>,
+}
+
+impl<'a> ConversionFailures<'a> {
+    fn new(column: &'a str, target: String) -> Self {
+        Self {
+            column,
+            target,
+            rejected: Vec::new(),
+        }
+    }
+
+    /// Record a failed conversion (`row` is 1-based)
+    fn record(&mut self, row: usize, value: &Value) {
+        let reason = format!("cannot be converted to {}", self.target);
+        self.reject(row, value, reason);
+    }
+
+    /// Record a failed conversion with a specific reason
+    fn reject(&mut self, row: usize, value: &Value, reason: String) {
+        self.rejected.push(Reject { row, value: value.to_string(), reason });
+    }
+
+    /// Log a summary and pass the rejected values to the reject log
+    fn report(&self, rejects: &RejectLog) -> Result<()> {
+        if let Some(first) = self.rejected.first() {
+            tracing::warn!(
+                "Column '{}': {} values could not be converted to {} and were stored as null (first at row {}: {})",
+                self.column, self.rejected.len(), self.target, first.row, first.value
+            );
+        }
+        rejects.record(self.column, &self.target, &self.rejected)
+    }
 }
 
 impl VortexWriter {
     /// Create a new VortexWriter instance
     /// 
     /// # Arguments
-    /// * `skip_lobs` - If true, LOB columns (CLOB, BLOB, etc.) will be excluded from output
-    pub fn new(skip_lobs: bool) -> Self {
+    /// * `config` - Conversion options (LOB handling, column type overrides)
+    pub fn new(config: WriterConfig) -> Self {
         Self {
             field_order: Vec::new(),
+            known_fields: HashSet::new(),
+            columns: Vec::new(),
+            types: TypeCatalog::default(),
+            rejects: RejectLog::new(config.rejects_path.clone(), config.max_rejects, config.strict),
+            batch: 0,
             records: Vec::new(),
-            skip_lobs,
+            config,
         }
     }
 
+    /// Set the definitions used to decode object type, VARRAY and nested table columns
+    pub fn set_type_catalog(&mut self, types: TypeCatalog) {
+        self.types = types;
+    }
+
+    /// Mark the start of a new auto-batch
+    ///
+    /// Columns that appear after the first batch are reported as schema drift.
+    pub fn start_batch(&mut self) {
+        self.batch += 1;
+    }
+
+    /// Provide the column metadata SQLcl reported for the query (or current batch)
+    ///
+    /// The metadata gives the SELECT order of the columns, which the keys of the
+    /// JSON records do not preserve. Columns added or missing compared to earlier
+    /// batches are reported as schema drift.
+    pub fn set_columns(&mut self, columns: &[ColumnInfo]) -> Result<()> {
+        if self.columns.is_empty() {
+            self.columns = columns.to_vec();
+            return Ok(());
+        }
+        if columns.is_empty() {
+            return Ok(());
+        }
+
+        let added: Vec<&ColumnInfo> = columns
+            .iter()
+            .filter(|c| !self.columns.iter().any(|k| k.name.eq_ignore_ascii_case(&c.name)))
+            .collect();
+        let missing: Vec<&str> = self.columns
+            .iter()
+            .filter(|k| !columns.iter().any(|c| c.name.eq_ignore_ascii_case(&k.name)))
+            .map(|k| k.name.as_str())
+            .collect();
+
+        if !added.is_empty() || !missing.is_empty() {
+            let added_names: Vec<&str> = added.iter().map(|c| c.name.as_str()).collect();
+            self.schema_drift(format!(
+                "columns added: {:?}, columns missing: {:?}",
+                added_names, missing
+            ))?;
+            let added: Vec<ColumnInfo> = added.into_iter().cloned().collect();
+            self.columns.extend(added);
+        }
+
+        Ok(())
+    }
+
+    /// Report a schema change between batches according to the drift policy
+    fn schema_drift(&self, details: String) -> Result<()> {
+        match self.config.schema_drift {
+            SchemaDrift::Warn => {
+                tracing::warn!("Schema drift in batch {}: {} (keeping the union of all columns)", self.batch, details);
+                Ok(())
+            }
+            SchemaDrift::Error => anyhow::bail!("Schema drift in batch {}: {}", self.batch, details),
+        }
+    }
+
+    /// Whether the column metadata describes a record key
+    fn is_described(&self, key: &str) -> bool {
+        self.columns.iter().any(|c| c.name.eq_ignore_ascii_case(key))
+    }
+
+    /// Columns to write: every record key seen plus described columns that were
+    /// never present in a record, ordered by the column metadata
+    fn output_fields(&self) -> Vec<String> {
+        let mut fields = self.field_order.clone();
+        for column in &self.columns {
+            let key = column.name.to_lowercase();
+            let seen = self.field_order.iter().any(|f| f.eq_ignore_ascii_case(&column.name));
+            if !seen {
+                fields.push(key);
+            }
+        }
+        self.order_by_metadata(fields)
+    }
+
+    /// Output fields kept by the include/exclude patterns, before any LOB
+    /// handling: an excluded LOB column is neither checked nor truncated
+    ///
+    /// A `<COL>__LOB_LENGTH` helper column follows the selection of its LOB column.
+    fn selected_fields(&self) -> Vec<String> {
+        let fields = self.output_fields();
+        let total = fields.len();
+        let selected: Vec<String> = fields
+            .into_iter()
+            .filter(|field| {
+                let column = lobs::lob_length_target(field)
+                    .filter(|_| self.config.verify_lob_lengths)
+                    .unwrap_or(field);
+                self.config.layout.is_selected(column)
+            })
+            .collect();
+        if selected.len() < total {
+            tracing::info!("Column selection: keeping {} of {} columns", selected.len(), total);
+        }
+        selected
+    }
+
+    /// Order record keys by their position in the column metadata
+    ///
+    /// Keys are matched case-insensitively; keys missing from the metadata keep
+    /// their relative order after the described ones.
+    fn order_by_metadata(&self, keys: Vec<String>) -> Vec<String> {
+        if self.columns.is_empty() {
+            return keys;
+        }
+
+        let mut keyed: Vec<(usize, String)> = keys
+            .into_iter()
+            .map(|key| {
+                let position = self.columns
+                    .iter()
+                    .position(|c| c.name.eq_ignore_ascii_case(&key))
+                    .unwrap_or(usize::MAX);
+                (position, key)
+            })
+            .collect();
+        keyed.sort_by_key(|(position, _)| *position);
+        keyed.into_iter().map(|(_, key)| key).collect()
+    }
+
     /// Check if a column value appears to be a LOB type based on heuristics
     /// Oracle LOBs in JSON export can be very long strings or have specific patterns
     fn is_likely_lob(value: &Value) -> bool {
@@ -45,58 +288,223 @@ impl VortexWriter {
         }
     }
 
-    /// Filter out LOB columns from a record
-    fn filter_lobs(&self, record: &Value) -> Value {
-        if !self.skip_lobs {
-            return record.clone();
-        }
+    /// Oracle type of a column, if SQLcl described it
+    fn oracle_type(&self, field_name: &str) -> Option<&str> {
+        self.columns
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(field_name))
+            .map(|c| c.oracle_type.as_str())
+            .filter(|t| !t.is_empty())
+    }
 
-        if let Some(obj) = record.as_object() {
-            let filtered: serde_json::Map<String, Value> = obj
+    /// Whether a column holds LOB data
+    ///
+    /// Decided from the Oracle type when SQLcl described the column, otherwise
+    /// from the values: the column is a LOB if any of its values looks like one.
+    /// Either way the decision covers the whole column, for every row and batch.
+    fn is_lob_column(&self, field_name: &str) -> bool {
+        match self.oracle_type(field_name) {
+            Some(oracle_type) => lobs::is_lob_type(oracle_type),
+            None => self.records
                 .iter()
-                .filter(|(_, v)| !Self::is_likely_lob(v))
-                .map(|(k, v)| (k.clone(), v.clone()))
-                .collect();
-            Value::Object(filtered)
+                .any(|r| r.get(field_name).map(Self::is_likely_lob).unwrap_or(false)),
+        }
+    }
+
+    /// Cut the values of a LOB column to at most `max_bytes` bytes
+    ///
+    /// Base64 BLOB values are decoded, cut and re-encoded so that they stay
+    /// valid base64.
+    fn truncate_lob_column(&mut self, field_name: &str, max_bytes: usize) -> Result<()> {
+        // Hex-encoded binary LOBs take two characters per byte
+        let binary = self.oracle_type(field_name).map(lobs::is_binary_lob_type).unwrap_or(false);
+        let base64 = binary && self.is_base64_blob(field_name);
+        let max_chars = if binary { max_bytes.saturating_mul(2) } else { max_bytes };
+        let decoded_length = |s: &str| lobs::decode_base64(s).map(|bytes| bytes.len());
+
+        let cut = self.records.iter().filter_map(|r| r.get(field_name)?.as_str()).find(|s| match base64 {
+            true => decoded_length(s).is_some_and(|len| len > max_bytes),
+            false => s.len() > max_chars,
+        });
+        if let Some(value) = cut {
+            let row = self.first_row_with(field_name, value);
+            let length = match decoded_length(value).filter(|_| base64) {
+                Some(len) => format!("{} bytes", len),
+                None => format!("{} characters", value.len()),
+            };
+            self.check_strict(field_name, row, &length, &format!("would be truncated to {} bytes", max_bytes))?;
+        }
+
+        let mut truncated = 0;
+        for record in &mut self.records {
+            if let Some(Value::String(s)) = record.as_object_mut().and_then(|obj| obj.get_mut(field_name)) {
+                if base64 {
+                    if let Some(mut bytes) = lobs::decode_base64(s).filter(|bytes| bytes.len() > max_bytes) {
+                        bytes.truncate(max_bytes);
+                        *s = lobs::encode_base64(&bytes);
+                        truncated += 1;
+                    }
+                } else if s.len() > max_chars {
+                    let cut = lobs::truncate_text(s, max_chars).len();
+                    s.truncate(cut);
+                    truncated += 1;
+                }
+            }
+        }
+
+        if truncated > 0 {
+            tracing::info!("LOB column '{}': truncated {} values to {} bytes", field_name, truncated, max_bytes);
+        }
+        Ok(())
+    }
+
+    /// Whether a BLOB column arrives base64-encoded rather than hex-encoded
+    ///
+    /// BLOBs are hex-encoded unless some value is not valid hex; the choice
+    /// covers the whole column so short base64 values that happen to look like
+    /// hex are not misread.
+    fn is_base64_blob(&self, field_name: &str) -> bool {
+        !self.records
+            .iter()
+            .filter_map(|r| r.get(field_name)?.as_str())
+            .all(|s| s.len().is_multiple_of(2) && s.chars().all(|c| c.is_ascii_hexdigit()))
+    }
+
+    /// Decoder for the text form of a binary column
+    ///
+    /// RAW values arrive hex-encoded, BLOBs hex- or base64-encoded (see
+    /// `is_base64_blob`).
+    fn binary_decoder(&self, field_name: &str) -> fn(&str) -> Option<Vec<u8>> {
+        let is_blob = self.oracle_type(field_name).map(lobs::is_binary_lob_type).unwrap_or(false);
+        if is_blob && self.is_base64_blob(field_name) {
+            lobs::decode_base64
         } else {
-            record.clone()
+            Self::hex_to_binary
+        }
+    }
+
+    /// Check LOB values against the `<COL>__LOB_LENGTH` columns added by
+    /// `--verify-lob-lengths` and remove those helper columns
+    ///
+    /// Oracle reports CLOB lengths in characters and BLOB lengths in bytes; a
+    /// shorter value means SQLcl cut it (see `--long` / `--long-chunk-size`).
+    fn check_lob_lengths(&self, fields: Vec<String>) -> Result<Vec<String>> {
+        if !self.config.verify_lob_lengths {
+            return Ok(fields);
+        }
+
+        let (helpers, fields): (Vec<String>, Vec<String>) = fields
+            .into_iter()
+            .partition(|f| lobs::lob_length_target(f).is_some());
+
+        for helper in &helpers {
+            let Some(target) = lobs::lob_length_target(helper) else { continue };
+            let Some(column) = fields.iter().find(|f| f.eq_ignore_ascii_case(target)) else { continue };
+
+            let binary = self.oracle_type(column).map(lobs::is_binary_lob_type).unwrap_or(false);
+            let decode = self.binary_decoder(column);
+
+            let mut truncated = 0;
+            let mut first = None;
+            for (row, record) in self.records.iter().enumerate() {
+                let Some(expected) = record.get(helper.as_str()).and_then(Value::as_u64) else { continue };
+                let Some(text) = record.get(column.as_str()).and_then(Value::as_str) else { continue };
+                let actual = if binary {
+                    decode(text).map(|bytes| bytes.len()).unwrap_or(0)
+                } else {
+                    text.chars().count()
+                } as u64;
+
+                if actual < expected {
+                    truncated += 1;
+                    first.get_or_insert((row + 1, actual, expected));
+                }
+            }
+
+            if let Some((row, actual, expected)) = first {
+                let unit = if binary { "bytes" } else { "characters" };
+                let value = format!("{} of {} {}", actual, expected, unit);
+                self.check_strict(column, row, &value, "was truncated by SQLcl")?;
+                tracing::warn!(
+                    "LOB column '{}': {} values were truncated by SQLcl (first at row {}: {}); \
+                     raise --long / --long-chunk-size",
+                    column, truncated, row, value
+                );
+            } else {
+                tracing::info!("LOB column '{}': all values complete", column);
+            }
         }
+
+        Ok(fields)
+    }
+
+    /// Apply the LOB policy: drop skipped LOB columns and truncate others in place
+    ///
+    /// Returns the columns that remain to be written.
+    fn apply_lob_policy(&mut self, fields: Vec<String>) -> Result<Vec<String>> {
+        let mut kept = Vec::with_capacity(fields.len());
+        let mut skipped = Vec::new();
+
+        for field in fields {
+            if self.is_lob_column(&field) {
+                match self.config.lobs.mode_for(&field) {
+                    LobMode::Skip => {
+                        skipped.push(field);
+                        continue;
+                    }
+                    LobMode::Truncate(max_bytes) => self.truncate_lob_column(&field, max_bytes)?,
+                    LobMode::Keep | LobMode::External(_) => {}
+                }
+            }
+            kept.push(field);
+        }
+
+        if !skipped.is_empty() {
+            tracing::info!(
+                "Skipping {} LOB columns (keeping {} columns): {:?}",
+                skipped.len(),
+                kept.len(),
+                skipped
+            );
+        }
+
+        Ok(kept)
     }
 
     /// Add a JSON record to the buffer
     /// 
     /// Records are accumulated in memory and written when `flush()` is called.
-    /// LOB columns are filtered out if `skip_lobs` was set to true.
+    /// The schema is the union of the keys of all records, so columns that
+    /// only appear in later records are kept.
     /// 
     /// # Arguments
     /// * `record` - A JSON value representing one row from Oracle
     pub async fn add_record(&mut self, record: Value) -> Result<()> {
-        // Filter LOBs if skip_lobs is enabled
-        let filtered_record = self.filter_lobs(&record);
-        
-        if self.field_order.is_empty() {
-            if let Some(obj) = filtered_record.as_object() {
-                self.field_order = obj.keys().cloned().collect();
-                
-                if self.skip_lobs {
-                    let original_count = record.as_object().map(|o| o.len()).unwrap_or(0);
-                    let filtered_count = obj.len();
-                    if original_count > filtered_count {
-                        tracing::info!(
-                            "Skipping {} LOB columns (keeping {} columns)",
-                            original_count - filtered_count,
-                            filtered_count
-                        );
+        if let Some(obj) = record.as_object() {
+            let first_record = self.records.is_empty();
+
+            for key in obj.keys() {
+                if self.known_fields.contains(key) {
+                    continue;
+                }
+                if !first_record {
+                    if self.batch > 1 && !self.is_described(key) {
+                        self.schema_drift(format!("new column '{}'", key))?;
+                    } else {
+                        tracing::debug!("Column '{}' first appears at record {}", key, self.records.len() + 1);
                     }
                 }
+                self.known_fields.insert(key.clone());
+                self.field_order.push(key.clone());
             }
         }
         
-        self.records.push(filtered_record);
+        self.records.push(record);
         Ok(())
     }
 
-    fn infer_dtype(value: &Value) -> DType {
+    /// Infer the Vortex dtype of a value, applying only the enabled heuristics to strings
+    fn infer_dtype(value: &Value, heuristics: Heuristics) -> DType {
         match value {
             Value::Null => DType::Utf8(Nullability::Nullable),
             Value::Bool(_) => DType::Bool(Nullability::Nullable),
@@ -110,7 +518,7 @@ impl VortexWriter {
             Value::String(s) => {
                 // Detect ISO 8601 date/timestamp patterns
                 // Check for timezone first (more specific pattern)
-                if Self::is_iso_timestamp_tz(s) {
+                if heuristics.timestamps && Self::is_iso_timestamp_tz(s) {
                     // Timestamp with timezone: YYYY-MM-DDTHH:MI:SS.FF+HH:MM or Z
                     if let Some(tz) = Self::extract_timezone(s) {
                         let metadata = TemporalMetadata::Timestamp(TimeUnit::Microseconds, Some(tz));
@@ -124,13 +532,13 @@ impl VortexWriter {
                         // Fallback if timezone extraction fails
                         DType::Utf8(Nullability::Nullable)
                     }
-                } else if Self::is_interval_day_to_second(s) {
-                    // INTERVAL DAY TO SECOND: +DD HH:MI:SS.FF → I64 microseconds
-                    DType::Primitive(PType::I64, Nullability::Nullable)
-                } else if Self::is_interval_year_to_month(s) {
-                    // INTERVAL YEAR TO MONTH: +YY-MM → I32 months
-                    DType::Primitive(PType::I32, Nullability::Nullable)
-                } else if Self::is_iso_date(s) {
+                } else if heuristics.intervals && Self::is_interval_day_to_second(s) {
+                    // INTERVAL DAY TO SECOND: +DD HH:MI:SS.FF → interval {days, nanos}
+                    interval::interval_dtype(IntervalKind::DayToSecond, Nullability::Nullable)
+                } else if heuristics.intervals && Self::is_interval_year_to_month(s) {
+                    // INTERVAL YEAR TO MONTH: +YY-MM → interval {months}
+                    interval::interval_dtype(IntervalKind::YearToMonth, Nullability::Nullable)
+                } else if heuristics.dates && Self::is_iso_date(s) {
                     // Pure date: YYYY-MM-DD
                     let metadata = TemporalMetadata::Date(TimeUnit::Days);
                     let ext_dtype = ExtDType::new(
@@ -139,7 +547,7 @@ impl VortexWriter {
                         Some(metadata.into()),
                     );
                     DType::Extension(Arc::new(ext_dtype))
-                } else if Self::is_iso_timestamp(s) {
+                } else if heuristics.timestamps && Self::is_iso_timestamp(s) {
                     // Timestamp without timezone
                     let metadata = TemporalMetadata::Timestamp(TimeUnit::Microseconds, None);
                     let ext_dtype = ExtDType::new(
@@ -148,12 +556,12 @@ impl VortexWriter {
                         Some(metadata.into()),
                     );
                     DType::Extension(Arc::new(ext_dtype))
-                } else if Self::is_hex_string(s) {
+                } else if heuristics.binary && Self::is_hex_string(s) {
                     // RAW/LONG RAW data (hex encoded)
                     DType::Binary(Nullability::Nullable)
-                } else if Self::is_valid_json(s) {
+                } else if heuristics.json && Self::is_valid_json(s) {
                     // JSON data (Oracle 21c+)
-                    // For now, keep as string - future: could parse structure
+                    // Kept as string; --nested-json handles whole JSON columns before inference
                     DType::Utf8(Nullability::Nullable)
                 } else {
                     DType::Utf8(Nullability::Nullable)
@@ -180,88 +588,85 @@ impl VortexWriter {
         }
         
         // Check if it has timezone (would be handled by is_iso_timestamp_tz)
-        let has_tz = s.ends_with('Z') || 
-                      s.rfind('+').map(|i| i > 19).unwrap_or(false) ||
-                      s.matches('-').count() > 2; // More than date hyphens = timezone
-        
-        if has_tz {
+        if Self::split_timezone(s).is_some() {
             return false; // Let is_iso_timestamp_tz handle it
         }
         
-        DateTime::strptime("%Y-%m-%dT%H:%M:%S", &s[..19.min(s.len())]).is_ok()
+        s.get(..19).map(|base| DateTime::strptime("%Y-%m-%dT%H:%M:%S", base).is_ok()).unwrap_or(false)
     }
 
     /// Check if a string is an ISO 8601 timestamp with timezone
     fn is_iso_timestamp_tz(s: &str) -> bool {
-        if !s.contains('T') || s.len() < 20 {
-            return false;
+        s.contains('T') && s.len() >= 20 && Self::split_timezone(s).is_some()
+    }
+
+    /// Split a timestamp into its date-time part and its time zone
+    ///
+    /// The zone is `Z`, an offset (`+02:00`, with or without a leading space) or,
+    /// after a space, an Oracle region name as produced by `TZR` (`Europe/Paris`).
+    fn split_timezone(s: &str) -> Option<(&str, &str)> {
+        let tail = s.get(19..)?;
+        if let Some(base) = s.strip_suffix('Z') {
+            return Some((base, "Z"));
         }
-        
-        // Check for timezone indicators
-        s.ends_with('Z') ||  // UTC indicator
-        s.contains(" +") || s.contains(" -") || // Oracle TZ format with space
-        (s.rfind('+').map(|i| i >= 19).unwrap_or(false)) || // +HH:MM after timestamp (>= not >)
-        (s.rfind('-').map(|i| i >= 19).unwrap_or(false) && s.matches('-').count() > 2)
+
+        if let Some(pos) = tail.find(' ') {
+            let (base, tz) = (&s[..19 + pos], tail[pos..].trim());
+            let is_offset = tz.starts_with(['+', '-']);
+            let is_region = tz.starts_with(|c: char| c.is_ascii_alphabetic())
+                && tz.chars().all(|c| c.is_ascii_alphanumeric() || "/_+-".contains(c));
+            return (is_offset || is_region).then_some((base, tz));
+        }
+
+        // Offset attached to the time: 2024-01-01T00:00:00.000+02:00
+        let pos = tail.rfind(['+', '-'])?;
+        Some((&s[..19 + pos], &tail[pos..]))
     }
 
     /// Extract timezone string from ISO timestamp
     fn extract_timezone(s: &str) -> Option<String> {
-        if s.ends_with('Z') {
-            return Some("UTC".to_string());
-        }
-        
-        // Look for +/-HH:MM or space +/-HH:MM (Oracle format)
-        if let Some(pos) = s.rfind(" +").or_else(|| s.rfind(" -")) {
-            // Oracle format with space: "2024-01-01T12:00:00.000000 +02:00"
-            return Some(s[pos+1..].trim().to_string());
-        }
-        
-        if let Some(pos) = s.rfind('+') {
-            if pos >= 19 { // After or at end of basic timestamp part
-                return Some(s[pos..].to_string());
-            }
-        }
-        
-        if let Some(pos) = s.rfind('-') {
-            if pos >= 19 && s.matches('-').count() > 2 { // More than date hyphens
-                return Some(s[pos..].to_string());
-            }
+        match Self::split_timezone(s)? {
+            (_, "Z") => Some("UTC".to_string()),
+            (_, tz) => Some(tz.to_string()),
         }
-        
-        None
     }
 
     /// Parse Oracle timezone format to get UTC timestamp
     fn parse_oracle_tz_format(s: &str) -> Option<i64> {
-        // Oracle format: YYYY-MM-DDTHH:MM:SS.FF +HH:MM or -HH:MM
-        // Split timestamp and timezone
-        let parts: Vec<&str> = if s.contains(" +") || s.contains(" -") {
-            s.splitn(2, ' ').collect()
-        } else if let Some(stripped) = s.strip_suffix('Z') {
-            // UTC timezone, just remove Z and parse
-            return Self::parse_timestamp_to_micros(stripped);
-        } else {
-            // Find last + or - that's not part of the date
-            if let Some(pos) = s.rfind('+').or_else(|| s.rfind('-').filter(|&p| p > 19)) {
-                vec![&s[..pos], &s[pos..]]
-            } else {
-                return None;
-            }
-        };
-        
-        if parts.len() != 2 {
-            return None;
-        }
-        
+        let (seconds, nanos) = Self::parse_oracle_tz_parts(s)?;
+        Self::timestamp_in_unit(seconds, nanos, TimeUnit::Microseconds)
+    }
+
+    /// Split a timestamp with time zone into UTC seconds since epoch and the
+    /// nanosecond fraction
+    fn parse_oracle_tz_parts(s: &str) -> Option<(i64, u32)> {
+        // Oracle format: YYYY-MM-DDTHH:MM:SS.FF followed by Z, +HH:MM / -HH:MM or a region
+        let (base, tz) = Self::split_timezone(s)?;
+        let base = base.trim();
+
         // Parse base timestamp without timezone
-        let base_micros = Self::parse_timestamp_to_micros(parts[0].trim())?;
-        
-        // Parse timezone offset (e.g., "+02:00" or "-05:30")
-        let tz_str = parts[1].trim();
-        let tz_offset_secs = Self::parse_tz_offset(tz_str)?;
-        
-        // Convert to UTC by subtracting the offset
-        Some(base_micros - (tz_offset_secs * 1_000_000))
+        let (seconds, nanos) = Self::parse_timestamp_parts(base)?;
+
+        // Convert to UTC by subtracting the offset in effect at that local time
+        let offset = Self::tz_offset_at(tz, base)?;
+        Some((seconds - offset, nanos))
+    }
+
+    /// Offset in seconds of a time zone (`Z`, `±HH:MM` or a region name) at a local date-time
+    ///
+    /// Region names are resolved with the system time zone database; a local time
+    /// repeated by a DST change resolves to the earlier instant.
+    fn tz_offset_at(tz: &str, local: &str) -> Option<i64> {
+        if tz == "Z" {
+            return Some(0);
+        }
+        if let Some(offset) = Self::parse_tz_offset(tz) {
+            return Some(offset);
+        }
+
+        let local = DateTime::strptime("%Y-%m-%dT%H:%M:%S", local.get(..19)?).ok()?;
+        let zoned = TimeZone::get(tz).ok()?.to_ambiguous_zoned(local).compatible().ok()?;
+        Some(zoned.offset().seconds() as i64)
     }
 
     /// Parse timezone offset string to seconds (e.g., "+02:00" -> 7200)
@@ -406,53 +811,1053 @@ impl VortexWriter {
         Some(sign * (years * 12 + months))
     }
 
-    /// Check if a string is valid JSON
-    fn is_valid_json(s: &str) -> bool {
-        // Quick check: must start with { or [
-        if !s.starts_with('{') && !s.starts_with('[') {
-            return false;
+    /// Check if a string is valid JSON
+    fn is_valid_json(s: &str) -> bool {
+        // Quick check: must start with { or [
+        if !s.starts_with('{') && !s.starts_with('[') {
+            return false;
+        }
+        
+        // Try to parse as JSON
+        serde_json::from_str::<serde_json::Value>(s).is_ok()
+    }
+
+    /// Parse ISO 8601 date to days since epoch
+    fn parse_date_to_days(s: &str) -> Option<i32> {
+        let date = Date::strptime("%Y-%m-%d", s).ok()?;
+        let epoch = Date::new(1970, 1, 1).ok()?;
+        Some(date.since(epoch).ok()?.get_days())
+    }
+
+    /// Parse ISO 8601 timestamp to microseconds since epoch
+    fn parse_timestamp_to_micros(s: &str) -> Option<i64> {
+        let (seconds, nanos) = Self::parse_timestamp_parts(s)?;
+        Self::timestamp_in_unit(seconds, nanos, TimeUnit::Microseconds)
+    }
+
+    /// Parse ISO 8601 timestamp to nanoseconds since epoch
+    ///
+    /// Returns None outside the range an i64 of nanoseconds can hold (years 1677 to 2262).
+    fn parse_timestamp_to_nanos(s: &str) -> Option<i64> {
+        let (seconds, nanos) = Self::parse_timestamp_parts(s)?;
+        Self::timestamp_in_unit(seconds, nanos, TimeUnit::Nanoseconds)
+    }
+
+    /// Split an ISO 8601 timestamp (YYYY-MM-DDTHH:MM:SS[.fffffffff]) into seconds
+    /// since epoch and the nanosecond fraction
+    fn parse_timestamp_parts(s: &str) -> Option<(i64, u32)> {
+        if s.len() < 19 {
+            return None;
+        }
+        
+        // Handle YYYY-MM-DDTHH:MM:SS.fffffffff format
+        let base_format = "%Y-%m-%dT%H:%M:%S";
+        let base_part = s.get(..19)?;
+        let dt = DateTime::strptime(base_format, base_part).ok()?;
+        
+        // Fractional seconds, up to nanoseconds (Oracle FF emits at most 9 digits)
+        let frac_part = Self::fraction_digits(s);
+        let nanos = if frac_part.is_empty() {
+            0
+        } else {
+            format!("{:0<9}", &frac_part[..frac_part.len().min(9)]).parse::<u32>().ok()?
+        };
+        
+        // Whole seconds since epoch (a span in seconds, not the default days/hours/... breakdown)
+        let epoch = DateTime::new(1970, 1, 1, 0, 0, 0, 0).ok()?;
+        let duration = dt.since((jiff::Unit::Second, epoch)).ok()?;
+        
+        Some((duration.get_seconds(), nanos))
+    }
+
+    /// Fractional-second digits of an ISO timestamp (empty if there are none)
+    fn fraction_digits(s: &str) -> &str {
+        match s.get(19..).and_then(|rest| rest.strip_prefix('.')) {
+            Some(rest) => {
+                let end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+                &rest[..end]
+            }
+            None => "",
+        }
+    }
+
+    /// Combine seconds since epoch and a nanosecond fraction into the given unit
+    ///
+    /// Digits finer than the unit are dropped; returns None on overflow.
+    fn timestamp_in_unit(seconds: i64, nanos: u32, unit: TimeUnit) -> Option<i64> {
+        let nanos = nanos as i64;
+        match unit {
+            TimeUnit::Nanoseconds => seconds.checked_mul(1_000_000_000)?.checked_add(nanos),
+            TimeUnit::Microseconds => seconds.checked_mul(1_000_000)?.checked_add(nanos / 1_000),
+            TimeUnit::Milliseconds => seconds.checked_mul(1_000)?.checked_add(nanos / 1_000_000),
+            TimeUnit::Seconds => Some(seconds),
+            TimeUnit::Days => Some(seconds.div_euclid(86_400)),
+        }
+    }
+
+    /// Number of fractional-second digits a time unit keeps
+    fn unit_fraction_digits(unit: TimeUnit) -> usize {
+        match unit {
+            TimeUnit::Nanoseconds => 9,
+            TimeUnit::Microseconds => 6,
+            TimeUnit::Milliseconds => 3,
+            TimeUnit::Seconds | TimeUnit::Days => 0,
+        }
+    }
+
+    /// Fractional-second precision declared by an Oracle type such as
+    /// `TIMESTAMP(9)` or `TIMESTAMP(3) WITH TIME ZONE`
+    fn timestamp_scale(oracle_type: &str) -> Option<usize> {
+        let upper = oracle_type.trim().to_uppercase();
+        let rest = upper.strip_prefix("TIMESTAMP(")?;
+        rest[..rest.find(')')?].trim().parse().ok()
+    }
+
+    /// Precision and scale declared by `NUMBER(p)` or `NUMBER(p,s)`
+    fn number_precision(oracle_type: &str) -> Option<(u32, i32)> {
+        let upper = oracle_type.trim().to_uppercase();
+        let rest = upper.strip_prefix("NUMBER(")?;
+        let args = &rest[..rest.find(')')?];
+        let (precision, scale) = args.split_once(',').unwrap_or((args, "0"));
+        Some((precision.trim().parse().ok()?, scale.trim().parse().ok()?))
+    }
+
+    /// Narrowest integer type holding `digits` decimal digits
+    fn integer_ptype_for_digits(digits: u32) -> Option<PType> {
+        match digits {
+            0..=2 => Some(PType::I8),
+            3..=4 => Some(PType::I16),
+            5..=9 => Some(PType::I32),
+            10..=18 => Some(PType::I64),
+            _ => None,
+        }
+    }
+
+    /// Narrowest integer type holding every value in `min..=max`
+    fn integer_ptype_for_range(min: i64, max: i64) -> PType {
+        let fits = |lo: i64, hi: i64| min >= lo && max <= hi;
+        if fits(i8::MIN as i64, i8::MAX as i64) {
+            PType::I8
+        } else if fits(i16::MIN as i64, i16::MAX as i64) {
+            PType::I16
+        } else if fits(i32::MIN as i64, i32::MAX as i64) {
+            PType::I32
+        } else {
+            PType::I64
+        }
+    }
+
+    /// Target width of an inferred numeric column
+    ///
+    /// Integers take the width of their `NUMBER(p,0)` precision or, without a
+    /// declared precision and with `narrow_by_values`, of the range of their
+    /// values. BINARY_FLOAT columns are F32.
+    fn numeric_ptype(&self, field_name: &str, ptype: PType) -> Option<PType> {
+        let oracle_type = self.oracle_type(field_name).unwrap_or("");
+        match ptype {
+            PType::I64 => match Self::number_precision(oracle_type) {
+                Some((precision, 0)) => Self::integer_ptype_for_digits(precision),
+                Some(_) => None,
+                None if !self.config.narrow_by_values => None,
+                None => {
+                    let values = self.records.iter().filter_map(|r| r.get(field_name)?.as_i64());
+                    let (min, max) = values.fold(None, |range: Option<(i64, i64)>, v| match range {
+                        Some((lo, hi)) => Some((lo.min(v), hi.max(v))),
+                        None => Some((v, v)),
+                    })?;
+                    Some(Self::integer_ptype_for_range(min, max))
+                }
+            },
+            PType::F64 if oracle_type.eq_ignore_ascii_case("BINARY_FLOAT") => Some(PType::F32),
+            _ => None,
+        }
+    }
+
+    /// Cast an inferred I64 or F64 column to the width of its Oracle type
+    fn narrow_numbers(&self, field_name: &str, array: ArrayRef) -> Result<ArrayRef> {
+        if self.config.wide_numbers {
+            return Ok(array);
+        }
+        let DType::Primitive(ptype, nullability) = *array.dtype() else {
+            return Ok(array);
+        };
+        let Some(target) = self.numeric_ptype(field_name, ptype).filter(|t| *t != ptype) else {
+            return Ok(array);
+        };
+        if target == PType::F32 {
+            self.check_float_precision(field_name, target)?;
+        }
+
+        Ok(match vortex_array::compute::cast(&array, &DType::Primitive(target, nullability)) {
+            Ok(narrowed) => {
+                tracing::debug!("Field '{}': narrowed {} to {}", field_name, ptype, target);
+                narrowed
+            }
+            Err(e) => {
+                tracing::warn!("Column '{}': values do not fit {} ({}), kept as {}", field_name, target, e, ptype);
+                array
+            }
+        })
+    }
+
+    /// With `--strict`, fail on a value that a float column of type `ptype`
+    /// cannot hold exactly: integers beyond the mantissa, or decimals that need
+    /// more digits than F32 keeps
+    fn check_float_precision(&self, field_name: &str, ptype: PType) -> Result<()> {
+        if !self.config.strict {
+            return Ok(());
+        }
+
+        for (row, record) in self.records.iter().enumerate() {
+            let Some(value) = record.get(field_name).filter(|v| !v.is_null()) else { continue };
+            let Some(f) = Self::value_to_f64(value) else { continue };
+            let integer = match value {
+                Value::Number(n) => n.as_i64().map(i128::from).or_else(|| n.as_u64().map(i128::from)),
+                Value::String(s) => s.trim().parse::<i128>().ok(),
+                _ => None,
+            };
+
+            let exact = match ptype {
+                PType::F32 if f.is_finite() => {
+                    let narrowed = f as f32;
+                    integer.is_none_or(|i| narrowed as i128 == i)
+                        && format!("{}", narrowed).parse::<f64>().is_ok_and(|back| back == f)
+                }
+                _ => integer.is_none_or(|i| f as i128 == i),
+            };
+            if !exact {
+                self.check_strict(field_name, row + 1, &value.to_string(), &format!("loses precision as {}", ptype))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Time unit for an inferred timestamp column
+    ///
+    /// Nanoseconds when the Oracle type declares more than 6 fractional digits
+    /// (`TIMESTAMP(7)` to `TIMESTAMP(9)`), or, without a declared scale, when a
+    /// value carries more than 6 significant fractional digits; microseconds
+    /// otherwise. Values outside the nanosecond range (years 1677 to 2262) force
+    /// microseconds.
+    fn timestamp_unit(&self, field_name: &str) -> TimeUnit {
+        let values: Vec<&str> = self.records
+            .iter()
+            .filter_map(|r| r.get(field_name)?.as_str())
+            .collect();
+
+        let needs_nanos = match self.oracle_type(field_name).and_then(Self::timestamp_scale) {
+            Some(scale) => scale > 6,
+            None => values
+                .iter()
+                .any(|s| Self::fraction_digits(s).trim_end_matches('0').len() > 6),
+        };
+        if !needs_nanos {
+            return TimeUnit::Microseconds;
+        }
+
+        // A value that parses at microsecond precision but overflows in nanoseconds
+        let out_of_range = values.iter().find(|s| {
+            if Self::is_iso_timestamp_tz(s) {
+                let nanos = Self::parse_oracle_tz_parts(s)
+                    .and_then(|(seconds, nanos)| Self::timestamp_in_unit(seconds, nanos, TimeUnit::Nanoseconds));
+                Self::parse_oracle_tz_format(s).is_some() && nanos.is_none()
+            } else {
+                Self::parse_timestamp_to_micros(s).is_some() && Self::parse_timestamp_to_nanos(s).is_none()
+            }
+        });
+        match out_of_range {
+            Some(value) => {
+                tracing::warn!(
+                    "Column '{}': {} is outside the nanosecond range, storing microseconds",
+                    field_name, value
+                );
+                TimeUnit::Microseconds
+            }
+            None => TimeUnit::Nanoseconds,
+        }
+    }
+
+    /// Vortex type of an Oracle DATE column according to `--oracle-date-as`
+    ///
+    /// A column is an Oracle DATE when the column metadata says so or, without
+    /// metadata, when it was inferred as a timestamp and every value has the
+    /// `YYYY-MM-DDTHH:MI:SS` shape of the session's NLS_DATE_FORMAT (TIMESTAMP
+    /// values carry a fraction). Returns None for other columns.
+    fn oracle_date_dtype(&self, field_name: &str, inferred: &DType, heuristics: Heuristics) -> Result<Option<DType>> {
+        let values: Vec<&str> = self.records
+            .iter()
+            .filter_map(|r| r.get(field_name)?.as_str())
+            .collect();
+
+        let is_date_column = match self.oracle_type(field_name) {
+            Some(oracle_type) => {
+                oracle_type.eq_ignore_ascii_case("DATE") && (heuristics.dates || heuristics.timestamps)
+            }
+            None => {
+                matches!(inferred, DType::Extension(ext) if ext.id() == &*TIMESTAMP_ID)
+                    && values.iter().all(|s| s.len() == 19 && Self::is_iso_timestamp(s))
+            }
+        };
+        if !is_date_column {
+            return Ok(None);
+        }
+
+        let is_midnight = |s: &&str| s.get(10..).map(|time| time == "T00:00:00").unwrap_or(true);
+        let as_date = match self.config.oracle_date_as {
+            OracleDateAs::Date => {
+                let with_time = values.iter().filter(|s| !is_midnight(s)).count();
+                if let Some(value) = values.iter().find(|s| !is_midnight(s)) {
+                    let row = self.first_row_with(field_name, value);
+                    self.check_strict(field_name, row, value, "has a time of day, dropped by --oracle-date-as date")?;
+                }
+                if with_time > 0 {
+                    tracing::warn!(
+                        "Column '{}': {} values have a time of day, dropped by --oracle-date-as date",
+                        field_name, with_time
+                    );
+                }
+                true
+            }
+            OracleDateAs::Timestamp => false,
+            OracleDateAs::Auto => values.iter().all(is_midnight),
+        };
+
+        let column_type = if as_date {
+            ColumnType::Date
+        } else {
+            ColumnType::Timestamp { unit: TimeUnit::Microseconds, tz: None }
+        };
+        tracing::debug!("Field '{}': Oracle DATE stored as {:?}", field_name, column_type);
+        Ok(Some(column_type.to_dtype(Nullability::Nullable)))
+    }
+
+    /// Time zone for the metadata of a timestamp column
+    ///
+    /// The zone of the values when they all share one; UTC when they differ, since
+    /// values are stored as UTC instants either way. None for timestamps without zone.
+    fn column_time_zone(&self, field_name: &str) -> Option<String> {
+        let zones: HashSet<String> = self.records
+            .iter()
+            .filter_map(|r| Self::extract_timezone(r.get(field_name)?.as_str()?))
+            .collect();
+
+        match zones.len() {
+            0 => None,
+            1 => zones.into_iter().next(),
+            n => {
+                tracing::info!(
+                    "Column '{}': values carry {} different time zones, column metadata set to UTC",
+                    field_name, n
+                );
+                Some("UTC".to_string())
+            }
+        }
+    }
+
+    /// With `--strict`, fail on a value whose zone differs from the column's zone,
+    /// since it is normalised to UTC and, without `--time-zone-columns`, lost
+    fn check_time_zones(&self, field_name: &str, column_zone: Option<&str>) -> Result<()> {
+        if !self.config.strict || self.config.time_zone_columns {
+            return Ok(());
+        }
+
+        for (row, record) in self.records.iter().enumerate() {
+            let Some(value) = record.get(field_name).and_then(Value::as_str) else { continue };
+            let Some(zone) = Self::extract_timezone(value) else { continue };
+            if Some(zone.as_str()) != column_zone {
+                let change = format!("is normalised to {} (use --time-zone-columns to keep the zone)", column_zone.unwrap_or("UTC"));
+                self.check_strict(field_name, row + 1, value, &change)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Add the `<column>_tz` companion column holding the original offset or region
+    /// of each value of a timestamp column
+    fn push_time_zone_column(
+        &self,
+        fields: &mut Vec<(String, ArrayRef)>,
+        output_columns: &[(String, String)],
+        field_name: &str,
+        output_name: &str,
+        array: &ArrayRef,
+    ) -> Result<()> {
+        let is_timestamp = matches!(array.dtype(), DType::Extension(ext) if ext.id() == &*TIMESTAMP_ID);
+        if !self.config.time_zone_columns || !is_timestamp || self.column_time_zone(field_name).is_none() {
+            return Ok(());
+        }
+
+        let name = format!("{}_tz", output_name);
+        if output_columns.iter().any(|(_, output)| *output == name) {
+            anyhow::bail!("Time zone column '{}' clashes with an existing column", name);
+        }
+
+        let zones: Vec<Option<String>> = self.records
+            .iter()
+            .map(|r| Self::extract_timezone(r.get(field_name)?.as_str()?))
+            .collect();
+        fields.push((name, VarBinArray::from_iter(zones, DType::Utf8(Nullability::Nullable)).into_array()));
+        Ok(())
+    }
+
+    /// Warn about timestamp values whose fraction is finer than the column's unit
+    fn report_precision_loss(&self, field_name: &str, unit: TimeUnit) -> Result<()> {
+        let kept = Self::unit_fraction_digits(unit);
+        let mut lost = 0;
+        let mut first = None;
+        for (row, record) in self.records.iter().enumerate() {
+            let Some(s) = record.get(field_name).and_then(Value::as_str) else { continue };
+            if Self::fraction_digits(s).trim_end_matches('0').len() > kept {
+                lost += 1;
+                first.get_or_insert((row + 1, s));
+            }
+        }
+
+        if let Some((row, value)) = first {
+            self.check_strict(field_name, row, value, &format!("loses precision when stored as {}", unit))?;
+            tracing::warn!(
+                "Column '{}': {} values lose precision when stored as {} (first at row {}: {})",
+                field_name, lost, unit, row, value
+            );
+        }
+        Ok(())
+    }
+
+    /// 1-based row of the first record holding this text in a column
+    fn first_row_with(&self, field_name: &str, value: &str) -> usize {
+        self.records
+            .iter()
+            .position(|r| r.get(field_name).and_then(Value::as_str) == Some(value))
+            .map_or(0, |row| row + 1)
+    }
+
+    /// With `--strict`, fail on a value that a conversion would change or drop
+    fn check_strict(&self, field_name: &str, row: usize, value: &str, change: &str) -> Result<()> {
+        if self.config.strict {
+            anyhow::bail!("Column '{}', row {}: {} {} (--strict)", field_name, row, value, change);
+        }
+        Ok(())
+    }
+
+    /// Convert a JSON value to an integer, accepting numeric strings and whole floats
+    fn value_to_i64(value: &Value) -> Option<i64> {
+        match value {
+            Value::Number(n) => n.as_i64().or_else(|| {
+                let f = n.as_f64()?;
+                (f.fract() == 0.0 && f >= i64::MIN as f64 && f < i64::MAX as f64).then_some(f as i64)
+            }),
+            Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Convert a JSON value to a float, accepting numeric strings and the
+    /// NaN/Inf tokens SQLcl uses for non-finite BINARY_DOUBLE values
+    fn value_to_f64(value: &Value) -> Option<f64> {
+        match value {
+            Value::Number(n) => n.as_f64(),
+            Value::String(s) => Self::parse_special_float(s).or_else(|| s.trim().parse().ok()),
+            _ => None,
+        }
+    }
+
+    /// Parse `NaN`, `Inf`, `-Inf` (also `Infinity`, `+Inf`), case-insensitively
+    fn parse_special_float(s: &str) -> Option<f64> {
+        match s.trim().to_lowercase().as_str() {
+            "nan" | "-nan" | "+nan" => Some(f64::NAN),
+            "inf" | "+inf" | "infinity" | "+infinity" => Some(f64::INFINITY),
+            "-inf" | "-infinity" => Some(f64::NEG_INFINITY),
+            _ => None,
+        }
+    }
+
+    /// Whether a column holds floats: BINARY_DOUBLE/BINARY_FLOAT, or numbers
+    /// mixed with NaN/Inf tokens (which would otherwise make it a string column)
+    ///
+    /// Tokens alone (a text column of `NaN` values) do not make a float column,
+    /// and columns without detection (`--strings-only`, `--no-detect-column`)
+    /// are only floats when declared so.
+    fn is_float_column(&self, field_name: &str) -> bool {
+        let oracle_type = self.oracle_type(field_name).unwrap_or("");
+        if oracle_type.eq_ignore_ascii_case("BINARY_DOUBLE") || oracle_type.eq_ignore_ascii_case("BINARY_FLOAT") {
+            return true;
+        }
+        if self.config.detection.for_column(field_name) == Heuristics::NONE {
+            return false;
+        }
+
+        let (mut special, mut number) = (false, false);
+        for value in self.records.iter().filter_map(|r| r.get(field_name)) {
+            match value {
+                Value::Null => {}
+                Value::Number(_) => number = true,
+                Value::String(s) if Self::parse_special_float(s).is_some() => special = true,
+                _ => return false,
+            }
+        }
+        special && number
+    }
+
+    /// Whether a numeric column has a non-integer value after integer ones, which
+    /// would otherwise make it an integer column from its first value
+    fn has_fractional_numbers(&self, field_name: &str) -> bool {
+        self.records
+            .iter()
+            .filter_map(|r| r.get(field_name)?.as_number())
+            .any(|n| n.as_i64().is_none() && n.as_u64().is_none() && n.as_f64().is_some_and(|f| f.fract() != 0.0))
+    }
+
+    /// Convert a JSON value to a boolean (true/false, 1/0)
+    fn value_to_bool(value: &Value) -> Option<bool> {
+        match value {
+            Value::Bool(b) => Some(*b),
+            Value::Number(n) => match n.as_i64()? {
+                0 => Some(false),
+                1 => Some(true),
+                _ => None,
+            },
+            Value::String(s) => match s.trim().to_lowercase().as_str() {
+                "true" | "1" => Some(true),
+                "false" | "0" => Some(false),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Convert a JSON value to the unscaled integer of a decimal(precision, scale)
+    ///
+    /// Returns None if the value has more fractional digits than `scale` allows
+    /// or does not fit in `precision` digits.
+    fn value_to_decimal(value: &Value, precision: u8, scale: i8) -> Option<i128> {
+        let text = match value {
+            Value::Number(n) => n.to_string(),
+            Value::String(s) => s.trim().to_string(),
+            _ => return None,
+        };
+        let unscaled = Self::parse_decimal(&text, scale)?;
+        (unscaled.unsigned_abs() < 10u128.checked_pow(precision as u32)?).then_some(unscaled)
+    }
+
+    /// Parse a decimal literal (with optional exponent) into an integer scaled by 10^scale
+    fn parse_decimal(text: &str, scale: i8) -> Option<i128> {
+        let (mantissa, exponent) = match text.find(['e', 'E']) {
+            Some(pos) => (&text[..pos], text[pos + 1..].parse::<i32>().ok()?),
+            None => (text, 0),
+        };
+        let (negative, mantissa) = match mantissa.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, mantissa.strip_prefix('+').unwrap_or(mantissa)),
+        };
+        let (int_part, frac_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        if int_part.is_empty() && frac_part.is_empty() {
+            return None;
+        }
+
+        let mut digits: i128 = 0;
+        for c in int_part.chars().chain(frac_part.chars()) {
+            digits = digits.checked_mul(10)?.checked_add(c.to_digit(10)? as i128)?;
+        }
+
+        // Number of places to shift `digits` left to reach the target scale
+        let shift = scale as i32 + exponent - frac_part.len() as i32;
+        let unscaled = if shift >= 0 {
+            digits.checked_mul(10i128.checked_pow(shift as u32)?)?
+        } else {
+            let divisor = 10i128.checked_pow((-shift) as u32)?;
+            if digits % divisor != 0 {
+                return None;
+            }
+            digits / divisor
+        };
+
+        Some(if negative { -unscaled } else { unscaled })
+    }
+
+    /// Convert a date or timestamp string to the given time unit since epoch
+    fn value_to_timestamp(value: &Value, unit: TimeUnit) -> Option<i64> {
+        let s = value.as_str()?;
+        let (seconds, nanos) = if Self::is_iso_date(s) {
+            (Self::parse_date_to_days(s)? as i64 * 86_400, 0)
+        } else if Self::is_iso_timestamp_tz(s) {
+            // A zone that cannot be resolved leaves the instant unknown
+            Self::parse_oracle_tz_parts(s)?
+        } else {
+            Self::parse_timestamp_parts(s)?
+        };
+
+        Self::timestamp_in_unit(seconds, nanos, unit)
+    }
+
+    /// Convert a date or timestamp string to days since epoch
+    fn value_to_date(value: &Value) -> Option<i32> {
+        let s = value.as_str()?;
+        if s.len() >= 10 && (s.len() == 10 || s.as_bytes()[10] == b'T') {
+            Self::parse_date_to_days(&s[..10])
+        } else {
+            None
+        }
+    }
+
+    /// Collect the values of one column through `convert`
+    ///
+    /// Values that fail to convert are stored as null and recorded in `failures`.
+    /// For non-nullable columns, nulls and failed conversions are errors.
+    fn collect_column<T: Default>(
+        &self,
+        field_name: &str,
+        nullable: bool,
+        failures: &mut ConversionFailures,
+        convert: impl Fn(&Value) -> Option<T>,
+    ) -> Result<(Vec<T>, Vec<bool>)> {
+        let mut values = Vec::with_capacity(self.records.len());
+        let mut validity = Vec::with_capacity(self.records.len());
+
+        for (idx, record) in self.records.iter().enumerate() {
+            let converted = match record.as_object().and_then(|obj| obj.get(field_name)) {
+                None | Some(Value::Null) => None,
+                Some(val) => {
+                    let converted = convert(val);
+                    if converted.is_none() {
+                        failures.record(idx + 1, val);
+                        if !nullable {
+                            anyhow::bail!(
+                                "Column '{}' is declared NOT NULL but row {} could not be converted to {}: {}",
+                                field_name, idx + 1, failures.target, val
+                            );
+                        }
+                    }
+                    converted
+                }
+            };
+
+            match converted {
+                Some(v) => {
+                    values.push(v);
+                    validity.push(true);
+                }
+                None => {
+                    if !nullable {
+                        anyhow::bail!("Column '{}' is declared NOT NULL but row {} is null", field_name, idx + 1);
+                    }
+                    values.push(T::default());
+                    validity.push(false);
+                }
+            }
+        }
+
+        Ok((values, validity))
+    }
+
+    /// Turn a per-row validity mask into a Validity for a column of the given nullability
+    fn validity_from_mask(mask: Vec<bool>, nullable: bool) -> Validity {
+        if nullable {
+            mask.into_iter().collect()
+        } else {
+            Validity::NonNullable
+        }
+    }
+
+    /// Build a column using a user-supplied type instead of the inferred one
+    fn build_override_column(&self, field_name: &str, column_override: &ColumnOverride) -> Result<ArrayRef> {
+        let dtype = column_override.dtype();
+        let nullable = column_override.nullable;
+        let mut failures = ConversionFailures::new(field_name, format!("{:?}", column_override.column_type));
+
+        let array = match &column_override.column_type {
+            ColumnType::Utf8 => {
+                let (values, validity) = self.collect_column(field_name, nullable, &mut failures, |val| {
+                    Some(match val {
+                        Value::String(s) => s.clone(),
+                        _ => val.to_string(),
+                    })
+                })?;
+                Self::varbin_from_parts(values, validity, dtype)
+            }
+            ColumnType::Binary => {
+                let decode = self.binary_decoder(field_name);
+                let (values, validity) = self.collect_column(field_name, nullable, &mut failures, |val| {
+                    decode(val.as_str()?)
+                })?;
+                Self::varbin_from_parts(values, validity, dtype)
+            }
+            ColumnType::I32 => {
+                let (values, validity) = self.collect_column(field_name, nullable, &mut failures, |val| {
+                    i32::try_from(Self::value_to_i64(val)?).ok()
+                })?;
+                PrimitiveArray::new(Buffer::from(values), Self::validity_from_mask(validity, nullable)).into_array()
+            }
+            ColumnType::I64 => {
+                let (values, validity) =
+                    self.collect_column(field_name, nullable, &mut failures, Self::value_to_i64)?;
+                PrimitiveArray::new(Buffer::from(values), Self::validity_from_mask(validity, nullable)).into_array()
+            }
+            ColumnType::F32 => {
+                let (values, validity) = self.collect_column(field_name, nullable, &mut failures, |val| {
+                    Self::value_to_f64(val).map(|f| f as f32)
+                })?;
+                self.check_float_precision(field_name, PType::F32)?;
+                PrimitiveArray::new(Buffer::from(values), Self::validity_from_mask(validity, nullable)).into_array()
+            }
+            ColumnType::F64 => {
+                let (values, validity) =
+                    self.collect_column(field_name, nullable, &mut failures, Self::value_to_f64)?;
+                self.check_float_precision(field_name, PType::F64)?;
+                PrimitiveArray::new(Buffer::from(values), Self::validity_from_mask(validity, nullable)).into_array()
+            }
+            ColumnType::Decimal { precision, scale } => {
+                let (values, validity) = self.collect_column(field_name, nullable, &mut failures, |val| {
+                    Self::value_to_decimal(val, *precision, *scale)
+                })?;
+                DecimalArray::new(Buffer::from(values), DecimalDType::new(*precision, *scale), Self::validity_from_mask(validity, nullable)).into_array()
+            }
+            ColumnType::Bool => {
+                let (values, validity) =
+                    self.collect_column(field_name, nullable, &mut failures, Self::value_to_bool)?;
+                let bits: vortex_buffer::BitBuffer = values.into();
+                BoolArray::new(bits, Self::validity_from_mask(validity, nullable)).into_array()
+            }
+            ColumnType::Date => {
+                let (values, validity) =
+                    self.collect_column(field_name, nullable, &mut failures, Self::value_to_date)?;
+                Self::extension_array(&dtype, PrimitiveArray::new(Buffer::from(values), Self::validity_from_mask(validity, nullable)).into_array())
+            }
+            ColumnType::Timestamp { unit, .. } => {
+                let (values, validity) = self.collect_column(field_name, nullable, &mut failures, |val| {
+                    Self::value_to_timestamp(val, *unit)
+                })?;
+                self.report_precision_loss(field_name, *unit)?;
+                Self::extension_array(&dtype, PrimitiveArray::new(Buffer::from(values), Self::validity_from_mask(validity, nullable)).into_array())
+            }
+            ColumnType::Vector { dimension, element } => {
+                self.build_vector_column(field_name, Some(*dimension), *element, nullable, &mut failures)?
+            }
+        };
+
+        failures.report(&self.rejects)?;
+        Ok(array)
+    }
+
+    /// Build a VarBinArray from collected values and their validity
+    fn varbin_from_parts<T: AsRef<[u8]>>(values: Vec<T>, mask: Vec<bool>, dtype: DType) -> ArrayRef {
+        let values: Vec<Option<T>> = values
+            .into_iter()
+            .zip(mask)
+            .map(|(v, valid)| valid.then_some(v))
+            .collect();
+        VarBinArray::from_iter(values, dtype).into_array()
+    }
+
+    /// Build a VECTOR column as a fixed-size list of `element`
+    ///
+    /// Without a declared dimension (`VECTOR(*)`), the length of the first vector
+    /// is used. Values with another length or components that do not fit the
+    /// element type are reported and stored as null.
+    fn build_vector_column(
+        &self,
+        field_name: &str,
+        dimension: Option<u32>,
+        element: VectorElement,
+        nullable: bool,
+        failures: &mut ConversionFailures,
+    ) -> Result<ArrayRef> {
+        let dimension = dimension.unwrap_or_else(|| {
+            self.records
+                .iter()
+                .find_map(|r| vector::parse_components(r.get(field_name)?))
+                .map(|c| c.len() as u32)
+                .unwrap_or(0)
+        });
+        tracing::debug!("Field '{}': vector({}, {:?})", field_name, dimension, element);
+
+        let (rows, validity) = self.collect_column(field_name, nullable, failures, |val| {
+            vector::convert(val, dimension, element)
+        })?;
+        Ok(vector::vector_array(rows, validity, dimension, element, nullable))
+    }
+
+    /// Parsed documents of a JSON column, when `nested_json` is enabled
+    ///
+    /// A column qualifies when Oracle reports it as JSON, or when every non-null
+    /// value is a JSON object or array (as emitted by SQLcl, or as text if the
+    /// `json` heuristic applies). Text that does not parse is kept as a string.
+    fn json_documents(&self, field_name: &str) -> Option<Vec<Option<Value>>> {
+        if !self.config.nested_json {
+            return None;
+        }
+        let declared = self.oracle_type(field_name).is_some_and(|t| t.eq_ignore_ascii_case("JSON"));
+        let parse_text = declared || self.config.detection.for_column(field_name).json;
+
+        let documents: Vec<Option<Value>> = self
+            .records
+            .iter()
+            .map(|r| match r.get(field_name)? {
+                Value::Null => None,
+                Value::String(s) if parse_text && Self::is_valid_json(s) => serde_json::from_str(s).ok(),
+                other => Some(other.clone()),
+            })
+            .collect();
+
+        let mut present = documents.iter().flatten().peekable();
+        let structured = present.peek().is_some() && present.all(|v| v.is_object() || v.is_array());
+        (declared || structured).then_some(documents)
+    }
+
+    /// Build a nested column from the documents of a JSON column
+    fn build_nested_json_column(field_name: &str, documents: &[Option<Value>]) -> ArrayRef {
+        let shape = Shape::infer(documents.iter().flatten());
+        tracing::debug!("Field '{}': nested JSON {:?}", field_name, shape);
+        for path in shape.text_paths() {
+            if path.is_empty() {
+                tracing::warn!("Field '{}': documents have no common shape, stored as JSON text", field_name);
+            } else {
+                tracing::warn!("Field '{}': heterogeneous values at '{}', stored as JSON text", field_name, path);
+            }
+        }
+
+        let values: Vec<Option<&Value>> = documents.iter().map(Option::as_ref).collect();
+        nested::build_array(&shape, &values)
+    }
+
+    /// Flag rule that applies to a column
+    ///
+    /// A rule given with `--bool-flag` always applies. The `--bool-flags` rule
+    /// applies to columns holding single-character text whose non-null values
+    /// all follow it, so that a code column is not nulled for looking like flags.
+    /// A column with some values that follow the rule and one that does not is
+    /// logged, naming that value.
+    fn flag_rule(&self, field_name: &str) -> Option<&FlagRule> {
+        if let Some(rule) = self.config.bool_flags.column_rule(field_name) {
+            return Some(rule);
+        }
+
+        let rule = self.config.bool_flags.global_rule()?;
+        let follows = |value: &Value| {
+            value.as_str().is_some_and(|s| s.trim().chars().count() == 1) && rule.convert(value).is_some()
+        };
+        let mut values = self.records.iter().filter_map(|r| r.get(field_name)).filter(|v| !v.is_null()).peekable();
+        values.peek()?;
+        match values.find(|value| !follows(value)) {
+            None => Some(rule),
+            Some(value) => {
+                if self.records.iter().filter_map(|r| r.get(field_name)).any(follows) {
+                    tracing::info!(
+                        "Column '{}': value {} does not follow the --bool-flags rule, column kept as text",
+                        field_name, value
+                    );
+                }
+                None
+            }
+        }
+    }
+
+    /// Build a Bool column; values `convert` rejects are reported and stored as null
+    fn build_bool_column(&self, field_name: &str, target: &str, convert: impl Fn(&Value) -> Option<bool>) -> Result<ArrayRef> {
+        let mut failures = ConversionFailures::new(field_name, target.to_string());
+        let (values, validity) = self.collect_column(field_name, true, &mut failures, convert)?;
+        failures.report(&self.rejects)?;
+
+        let bits: vortex_buffer::BitBuffer = values.into();
+        Ok(BoolArray::new(bits, Self::validity_from_mask(validity, true)).into_array())
+    }
+
+    /// Size of a RAW column: the declared `RAW(n)`, or, for a bare `RAW`, the
+    /// length shared by all of its values
+    fn raw_size(&self, field_name: &str) -> Option<u32> {
+        match raw::raw_size(self.oracle_type(field_name)?)? {
+            Some(size) => Some(size),
+            None => {
+                let mut lengths = self
+                    .records
+                    .iter()
+                    .filter_map(|r| r.get(field_name)?.as_str())
+                    .map(|s| s.len() / 2);
+                let first = lengths.next()?;
+                lengths.all(|len| len == first).then_some(first as u32).filter(|size| *size > 0)
+            }
+        }
+    }
+
+    /// Build a RAW(n) column as fixed-size binary, UUIDs or UUID text
+    ///
+    /// Values that are not hex or not `size` bytes long are reported and stored as null.
+    fn build_raw_column(&self, field_name: &str, size: u32) -> Result<ArrayRef> {
+        let mut failures = ConversionFailures::new(field_name, format!("RAW({})", size));
+        let (rows, validity) = self.collect_column(field_name, true, &mut failures, |val| {
+            val.as_str()
+                .and_then(Self::hex_to_binary)
+                .filter(|bytes| bytes.len() == size as usize)
+        })?;
+        failures.report(&self.rejects)?;
+
+        let raw16_as = if size == 16 { self.config.raw16_as } else { Raw16As::Binary };
+        tracing::debug!("Field '{}': RAW({}) as {:?}", field_name, size, raw16_as);
+        Ok(match raw16_as {
+            Raw16As::Binary => raw::fixed_binary_array(rows, validity, size),
+            Raw16As::Uuid => Self::extension_array(&raw::uuid_dtype(), raw::fixed_binary_array(rows, validity, size)),
+            Raw16As::Text => {
+                let text = rows.iter().zip(validity).map(|(bytes, valid)| valid.then(|| raw::format_uuid(bytes)));
+                VarBinArray::from_iter(text, DType::Utf8(Nullability::Nullable)).into_array()
+            }
+        })
+    }
+
+    /// Build a `geoarrow.wkb` column from SDO_GEOMETRY values
+    ///
+    /// The SRID goes into the column metadata when all geometries share one.
+    /// Geometries without a WKB equivalent (arcs, circles) are reported and stored as null.
+    fn build_geometry_column(&self, field_name: &str) -> Result<ArrayRef> {
+        let mut failures = ConversionFailures::new(field_name, "WKB".to_string());
+        let (values, validity) = self.collect_column(field_name, true, &mut failures, |val| {
+            let geometry = SdoGeometry::from_value(val)?;
+            Some((geometry.to_wkb()?, geometry.srid))
+        })?;
+        failures.report(&self.rejects)?;
+
+        let srids: HashSet<Option<i64>> = values.iter().zip(&validity).filter(|(_, valid)| **valid).map(|((_, srid), _)| *srid).collect();
+        let srid = match srids.len() {
+            1 => srids.into_iter().next().flatten(),
+            0 => None,
+            _ => {
+                tracing::warn!("Column '{}': geometries have different SRIDs, no CRS in the column metadata", field_name);
+                None
+            }
+        };
+        tracing::debug!("Field '{}': WKB geometry, SRID {:?}", field_name, srid);
+
+        let wkb = values.into_iter().zip(validity).map(|((wkb, _), valid)| valid.then_some(wkb));
+        let storage = VarBinArray::from_iter(wkb, DType::Binary(Nullability::Nullable)).into_array();
+        Ok(Self::extension_array(&geometry::wkb_dtype(srid), storage))
+    }
+
+    /// Directory holding the externalised LOB values of an output file (`<output>.lobs`)
+    fn lobs_dir(output_path: &Path) -> PathBuf {
+        let mut dir = output_path.as_os_str().to_owned();
+        dir.push(".lobs");
+        PathBuf::from(dir)
+    }
+
+    /// Size threshold of a LOB column in `external` mode
+    fn external_lob_threshold(&self, field_name: &str) -> Option<usize> {
+        if !self.is_lob_column(field_name) {
+            return None;
+        }
+        match self.config.lobs.mode_for(field_name) {
+            LobMode::External(threshold) => Some(threshold),
+            _ => None,
         }
-        
-        // Try to parse as JSON
-        serde_json::from_str::<serde_json::Value>(s).is_ok()
     }
 
-    /// Parse ISO 8601 date to days since epoch
-    fn parse_date_to_days(s: &str) -> Option<i32> {
-        let date = Date::strptime("%Y-%m-%d", s).ok()?;
-        let epoch = Date::new(1970, 1, 1).ok()?;
-        Some(date.since(epoch).ok()?.get_days())
+    /// Build a LOB column whose values are written to content-addressed files
+    /// under `<output>.lobs/`, when any value is larger than `threshold` bytes
+    ///
+    /// The column becomes a struct of `path` (relative to the directory of the
+    /// output file), `size` in bytes and `sha256` (hex). Identical values share
+    /// one file. Returns `None` when every value fits within the threshold: the
+    /// column is then written inline like any other.
+    async fn build_external_lob_column(
+        &self,
+        field_name: &str,
+        threshold: usize,
+        output_path: &Path,
+    ) -> Result<Option<ArrayRef>> {
+        let binary = self.oracle_type(field_name).map(lobs::is_binary_lob_type).unwrap_or(false);
+        let decode = self.binary_decoder(field_name);
+        let dir = Self::lobs_dir(output_path);
+        let dir_name = dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let values: Vec<Option<Vec<u8>>> = self
+            .records
+            .iter()
+            .map(|record| match record.get(field_name) {
+                Some(Value::String(s)) if binary => decode(s),
+                Some(Value::String(s)) => Some(s.as_bytes().to_vec()),
+                _ => None,
+            })
+            .collect();
+        if !values.iter().flatten().any(|bytes| bytes.len() > threshold) {
+            tracing::info!(
+                "LOB column '{}': no value over {} bytes, kept in the Vortex file",
+                field_name, threshold
+            );
+            return Ok(None);
+        }
+
+        let len = self.records.len();
+        let mut paths: Vec<Option<String>> = Vec::with_capacity(len);
+        let mut sizes: Vec<u64> = Vec::with_capacity(len);
+        let mut hashes: Vec<String> = Vec::with_capacity(len);
+        let mut mask = Vec::with_capacity(len);
+        let mut failures = ConversionFailures::new(field_name, "LOB bytes".to_string());
+        let (mut written, mut shared) = (0, 0);
+
+        for (row, bytes) in values.into_iter().enumerate() {
+            let Some(bytes) = bytes else {
+                if let Some(value) = self.records[row].get(field_name).filter(|v| !v.is_null()) {
+                    failures.record(row + 1, value);
+                }
+                paths.push(None);
+                sizes.push(0);
+                hashes.push(String::new());
+                mask.push(false);
+                continue;
+            };
+
+            let hash = format!("{:x}", Sha256::digest(&bytes));
+            let file = dir.join(&hash);
+            if tokio::fs::try_exists(&file).await.unwrap_or(false) {
+                shared += 1;
+            } else {
+                // Written under a temporary name, then renamed: a file named by
+                // its hash is always complete, so later runs can reuse it
+                tokio::fs::create_dir_all(&dir)
+                    .await
+                    .with_context(|| format!("Failed to create LOB directory {:?}", dir))?;
+                let tmp = dir.join(format!("{}.{}.tmp", hash, std::process::id()));
+                tokio::fs::write(&tmp, &bytes)
+                    .await
+                    .with_context(|| format!("Failed to write LOB file {:?}", tmp))?;
+                tokio::fs::rename(&tmp, &file)
+                    .await
+                    .with_context(|| format!("Failed to rename {:?} to {:?}", tmp, file))?;
+                written += 1;
+            }
+            paths.push(Some(format!("{}/{}", dir_name, hash)));
+            sizes.push(bytes.len() as u64);
+            hashes.push(hash);
+            mask.push(true);
+        }
+
+        failures.report(&self.rejects)?;
+        tracing::info!(
+            "LOB column '{}': {} files written to {:?} ({} values reused an existing file)",
+            field_name, written, dir, shared
+        );
+
+        let struct_array = StructArray::try_new(
+            FieldNames::from(["path", "size", "sha256"]),
+            vec![
+                VarBinArray::from_iter(paths, DType::Utf8(Nullability::Nullable)).into_array(),
+                PrimitiveArray::new(Buffer::from(sizes), Validity::NonNullable).into_array(),
+                VarBinArray::from_iter_nonnull(hashes, DType::Utf8(Nullability::NonNullable)).into_array(),
+            ],
+            len,
+            mask.into_iter().collect(),
+        )
+        .context("Failed to build externalised LOB column")?;
+
+        Ok(Some(struct_array.into_array()))
     }
 
-    /// Parse ISO 8601 timestamp to microseconds since epoch
-    fn parse_timestamp_to_micros(s: &str) -> Option<i64> {
-        // Try parsing with fractional seconds first
-        if s.len() < 19 {
-            return None;
+    /// Wrap a storage array in the extension dtype it was built for
+    fn extension_array(dtype: &DType, storage: ArrayRef) -> ArrayRef {
+        match dtype {
+            DType::Extension(ext) => ExtensionArray::new(ext.clone(), storage).into_array(),
+            _ => storage,
         }
-        
-        // Handle YYYY-MM-DDTHH:MM:SS.ffffff format
-        let base_format = "%Y-%m-%dT%H:%M:%S";
-        let base_part = &s[..19];
-        let dt = DateTime::strptime(base_format, base_part).ok()?;
-        
-        // Extract fractional seconds if present
-        let micros_fraction = if s.len() > 19 && s.chars().nth(19) == Some('.') {
-            let frac_part = s[20..].split(|c: char| !c.is_ascii_digit()).next().unwrap_or("0");
-            let frac_str = format!("{:0<6}", &frac_part[..frac_part.len().min(6)]);
-            frac_str.parse::<i64>().unwrap_or(0)
-        } else {
-            0
-        };
-        
-        // Convert to microseconds since epoch
-        let epoch = DateTime::new(1970, 1, 1, 0, 0, 0, 0).ok()?;
-        let duration = dt.since(epoch).ok()?;
-        
-        // Calculate total microseconds: (seconds * 1_000_000) + microseconds_fraction
-        let total_micros = duration.get_seconds() * 1_000_000 + micros_fraction;
-        
-        Some(total_micros)
     }
 
     /// Write all accumulated records to a Vortex file
@@ -471,6 +1876,12 @@ impl VortexWriter {
     /// - JSON (validated but kept as string)
     /// - Numeric types (INTEGER, FLOAT)
     /// - String fallback for all others
+    ///
+    /// String heuristics can be disabled globally or per column through the
+    /// detection policy in `WriterConfig`.
+    ///
+    /// Columns with a user-supplied type override skip detection and are converted
+    /// to that type; values that fail to convert are reported and stored as null.
     pub async fn flush<P: AsRef<Path>>(&mut self, output_path: P) -> Result<()> {
         if self.records.is_empty() {
             tracing::warn!("No records to write");
@@ -478,142 +1889,126 @@ impl VortexWriter {
         }
 
         tracing::info!("Writing {} records to Vortex file", self.records.len());
+        self.rejects.open()?;
+
+        // Resolve LOB columns of the selected fields, then build column arrays in output order
+        let fields = self.check_lob_lengths(self.selected_fields())?;
+        let fields = self.apply_lob_policy(fields)?;
+        let output_columns = self.config.layout.arrange(&fields)?;
+        let mut fields = Vec::new();
 
-        // Determine field order from first record if not set
-        if self.field_order.is_empty() {
-            if let Some(obj) = self.records[0].as_object() {
-                self.field_order = obj.keys().cloned().collect();
+        for (field_name, output_name) in &output_columns {
+            if let Some(threshold) = self.external_lob_threshold(field_name) {
+                if let Some(array) = self
+                    .build_external_lob_column(field_name, threshold, output_path.as_ref())
+                    .await?
+                {
+                    fields.push((output_name.clone(), array));
+                    continue;
+                }
             }
-        }
 
-        // Build column arrays
-        let mut fields = Vec::new();
+            if let Some(column_override) = self.config.schema.get(field_name) {
+                tracing::debug!("Field '{}': using override {:?}", field_name, column_override);
+                let array = self.build_override_column(field_name, column_override)?;
+                fields.push((output_name.clone(), array.clone()));
+                self.push_time_zone_column(&mut fields, &output_columns, field_name, output_name, &array)?;
+                continue;
+            }
+
+            // Oracle 23ai VECTOR columns, recognised from the column metadata
+            if let Some((dimension, element)) = self.oracle_type(field_name).and_then(vector::parse_oracle_type) {
+                let mut failures = ConversionFailures::new(field_name, format!("vector({:?})", element));
+                let array = self.build_vector_column(field_name, dimension, element, true, &mut failures)?;
+                failures.report(&self.rejects)?;
+                fields.push((output_name.clone(), array));
+                continue;
+            }
+
+            // RAW(n) columns are fixed-size binary (RAW(16) optionally UUID)
+            if let Some(size) = self.raw_size(field_name) {
+                fields.push((output_name.clone(), self.build_raw_column(field_name, size)?));
+                continue;
+            }
+
+            // SDO_GEOMETRY is converted to WKB
+            if self.oracle_type(field_name).is_some_and(geometry::is_geometry_type) {
+                fields.push((output_name.clone(), self.build_geometry_column(field_name)?));
+                continue;
+            }
+
+            // Object types, VARRAYs and nested tables follow their Oracle definition
+            if let Some((oracle_type, shape)) = self
+                .oracle_type(field_name)
+                .and_then(|t| Some((t, self.types.shape(t)?)))
+            {
+                tracing::debug!("Field '{}': {} as {:?}", field_name, oracle_type, shape);
+                let values: Vec<Option<Value>> = self
+                    .records
+                    .iter()
+                    .map(|r| r.get(field_name).map(|v| object_types::conform(&shape, v)))
+                    .collect();
+                let values: Vec<Option<&Value>> = values.iter().map(Option::as_ref).collect();
+                fields.push((output_name.clone(), nested::build_array(&shape, &values)));
+                continue;
+            }
+
+            // Opt-in: JSON documents become nested structs and lists
+            if let Some(documents) = self.json_documents(field_name) {
+                fields.push((output_name.clone(), Self::build_nested_json_column(field_name, &documents)));
+                continue;
+            }
+
+            // Oracle 23ai BOOLEAN, and flag columns opted in with --bool-flag(s)
+            if self.oracle_type(field_name).is_some_and(|t| t.eq_ignore_ascii_case("BOOLEAN")) {
+                fields.push((output_name.clone(), self.build_bool_column(field_name, "bool", Self::value_to_bool)?));
+                continue;
+            }
+            if let Some(rule) = self.flag_rule(field_name) {
+                tracing::debug!("Field '{}': flag column {:?}", field_name, rule);
+                let array = self.build_bool_column(field_name, "bool (flag)", |v| rule.convert(v))?;
+                fields.push((output_name.clone(), array));
+                continue;
+            }
 
-        for field_name in &self.field_order {
             // Infer dtype from first non-null value
-            let dtype = self.records.iter()
-                .find_map(|r| r.as_object()?.get(field_name))
-                .map(Self::infer_dtype)
-                .unwrap_or(DType::Utf8(Nullability::Nullable));
+            // BLOB columns are binary whatever their values look like
+            let heuristics = self.config.detection.for_column(field_name);
+            let is_blob = self.oracle_type(field_name).map(lobs::is_binary_lob_type).unwrap_or(false);
+            let dtype = if is_blob {
+                DType::Binary(Nullability::Nullable)
+            } else if self.is_float_column(field_name) {
+                DType::Primitive(PType::F64, Nullability::Nullable)
+            } else {
+                self.records.iter()
+                    .find_map(|r| r.as_object()?.get(field_name).filter(|v| !v.is_null()))
+                    .map(|v| Self::infer_dtype(v, heuristics))
+                    .unwrap_or(DType::Utf8(Nullability::Nullable))
+            };
+            let dtype = self.oracle_date_dtype(field_name, &dtype, heuristics)?.unwrap_or(dtype);
+            let dtype = match dtype {
+                DType::Primitive(PType::I64, n) if self.has_fractional_numbers(field_name) => DType::Primitive(PType::F64, n),
+                dtype => dtype,
+            };
 
             tracing::debug!("Field '{}': dtype={:?}, len={}", field_name, dtype, self.records.len());
 
             let array = match dtype {
                 DType::Primitive(PType::I64, _) => {
-                    let mut values = Vec::with_capacity(self.records.len());
-                    let mut validity = Vec::with_capacity(self.records.len());
-
-                    for record in &self.records {
-                        if let Some(obj) = record.as_object() {
-                            if let Some(val) = obj.get(field_name) {
-                                match val {
-                                    Value::Number(n) => {
-                                        values.push(n.as_i64().unwrap_or(0));
-                                        validity.push(true);
-                                    }
-                                    Value::String(s) => {
-                                        // Could be INTERVAL DAY TO SECOND
-                                        if let Some(micros) = Self::parse_interval_day_to_second(s) {
-                                            values.push(micros);
-                                            validity.push(true);
-                                        } else {
-                                            values.push(0);
-                                            validity.push(false);
-                                        }
-                                    }
-                                    Value::Null => {
-                                        values.push(0);
-                                        validity.push(false);
-                                    }
-                                    _ => {
-                                        values.push(0);
-                                        validity.push(false);
-                                    }
-                                }
-                            } else {
-                                values.push(0);
-                                validity.push(false);
-                            }
-                        }
-                    }
-
-                    let buffer = Buffer::from(values);
-                    let validity: Validity = validity.into_iter().collect();
-                    PrimitiveArray::new(buffer, validity).into_array()
-                }
-                DType::Primitive(PType::I32, _) => {
-                    let mut values = Vec::with_capacity(self.records.len());
-                    let mut validity = Vec::with_capacity(self.records.len());
-
-                    for record in &self.records {
-                        if let Some(obj) = record.as_object() {
-                            if let Some(val) = obj.get(field_name) {
-                                match val {
-                                    Value::Number(n) => {
-                                        values.push(n.as_i64().unwrap_or(0) as i32);
-                                        validity.push(true);
-                                    }
-                                    Value::String(s) => {
-                                        // Could be INTERVAL YEAR TO MONTH
-                                        if let Some(months) = Self::parse_interval_year_to_month(s) {
-                                            values.push(months);
-                                            validity.push(true);
-                                        } else {
-                                            values.push(0);
-                                            validity.push(false);
-                                        }
-                                    }
-                                    Value::Null => {
-                                        values.push(0);
-                                        validity.push(false);
-                                    }
-                                    _ => {
-                                        values.push(0);
-                                        validity.push(false);
-                                    }
-                                }
-                            } else {
-                                values.push(0);
-                                validity.push(false);
-                            }
-                        }
-                    }
-
-                    let buffer = Buffer::from(values);
-                    let validity: Validity = validity.into_iter().collect();
-                    PrimitiveArray::new(buffer, validity).into_array()
+                    let mut failures = ConversionFailures::new(field_name, "i64".to_string());
+                    let (values, validity) =
+                        self.collect_column(field_name, true, &mut failures, Self::value_to_i64)?;
+                    failures.report(&self.rejects)?;
+                    PrimitiveArray::new(Buffer::from(values), Self::validity_from_mask(validity, true)).into_array()
                 }
                 DType::Primitive(PType::F64, _) => {
-                    let mut values = Vec::with_capacity(self.records.len());
-                    let mut validity = Vec::with_capacity(self.records.len());
-
-                    for record in &self.records {
-                        if let Some(obj) = record.as_object() {
-                            if let Some(val) = obj.get(field_name) {
-                                match val {
-                                    Value::Number(n) => {
-                                        values.push(n.as_f64().unwrap_or(0.0));
-                                        validity.push(true);
-                                    }
-                                    Value::Null => {
-                                        values.push(0.0);
-                                        validity.push(false);
-                                    }
-                                    _ => {
-                                        values.push(0.0);
-                                        validity.push(false);
-                                    }
-                                }
-                            } else {
-                                values.push(0.0);
-                                validity.push(false);
-                            }
-                        }
-                    }
-
-                    let buffer = Buffer::from(values);
-                    let validity: Validity = validity.into_iter().collect();
-                    PrimitiveArray::new(buffer, validity).into_array()
+                    // NaN, Inf and -Inf arrive as strings; other strings are counted and nulled
+                    let mut failures = ConversionFailures::new(field_name, "f64".to_string());
+                    let (values, validity) =
+                        self.collect_column(field_name, true, &mut failures, Self::value_to_f64)?;
+                    failures.report(&self.rejects)?;
+                    self.check_float_precision(field_name, PType::F64)?;
+                    PrimitiveArray::new(Buffer::from(values), Self::validity_from_mask(validity, true)).into_array()
                 }
                 DType::Utf8(_) => {
                     let values: Vec<Option<String>> = self.records.iter()
@@ -630,137 +2025,66 @@ impl VortexWriter {
 
                     VarBinArray::from(values).into_array()
                 }
-                DType::Bool(_) => {
-                    let mut values = Vec::with_capacity(self.records.len());
-                    let mut validity = Vec::with_capacity(self.records.len());
-
-                    for record in &self.records {
-                        if let Some(obj) = record.as_object() {
-                            if let Some(val) = obj.get(field_name) {
-                                match val {
-                                    Value::Bool(b) => {
-                                        values.push(*b);
-                                        validity.push(true);
-                                    }
-                                    Value::Null => {
-                                        values.push(false);
-                                        validity.push(false);
-                                    }
-                                    _ => {
-                                        values.push(false);
-                                        validity.push(false);
-                                    }
-                                }
-                            } else {
-                                values.push(false);
-                                validity.push(false);
-                            }
-                        }
-                    }
-
-                    let validity: Validity = validity.into_iter().collect();
-                    let bits: vortex_buffer::BitBuffer = values.into();
-                    BoolArray::new(bits, validity).into_array()
-                }
+                DType::Bool(_) => self.build_bool_column(field_name, "bool", Self::value_to_bool)?,
                 DType::Extension(ref ext) if ext.id() == &*DATE_ID => {
                     // Handle Date type (days since epoch as I32)
-                    let mut values = Vec::with_capacity(self.records.len());
-                    let mut validity = Vec::with_capacity(self.records.len());
-
-                    for record in &self.records {
-                        if let Some(obj) = record.as_object() {
-                            if let Some(val) = obj.get(field_name) {
-                                match val {
-                                    Value::String(s) => {
-                                        if let Some(days) = Self::parse_date_to_days(s) {
-                                            values.push(days);
-                                            validity.push(true);
-                                        } else {
-                                            values.push(0);
-                                            validity.push(false);
-                                        }
-                                    }
-                                    Value::Null => {
-                                        values.push(0);
-                                        validity.push(false);
-                                    }
-                                    _ => {
-                                        values.push(0);
-                                        validity.push(false);
-                                    }
-                                }
-                            } else {
-                                values.push(0);
-                                validity.push(false);
-                            }
-                        }
-                    }
-
-                    let buffer = Buffer::from(values);
-                    let validity: Validity = validity.into_iter().collect();
-                    PrimitiveArray::new(buffer, validity).into_array()
+                    // Oracle DATE values carry a time part, dropped here
+                    let mut failures = ConversionFailures::new(field_name, "date".to_string());
+                    let (values, validity) =
+                        self.collect_column(field_name, true, &mut failures, Self::value_to_date)?;
+                    failures.report(&self.rejects)?;
+                    let storage = PrimitiveArray::new(Buffer::from(values), Self::validity_from_mask(validity, true));
+                    Self::extension_array(&dtype, storage.into_array())
                 }
                 DType::Extension(ref ext) if ext.id() == &*TIMESTAMP_ID => {
-                    // Handle Timestamp type (microseconds since epoch as I64)
+                    // Handle Timestamp type (microseconds or nanoseconds since epoch as I64)
                     // Supports both with and without timezone
-                    let mut values = Vec::with_capacity(self.records.len());
-                    let mut validity = Vec::with_capacity(self.records.len());
-
-                    for record in &self.records {
-                        if let Some(obj) = record.as_object() {
-                            if let Some(val) = obj.get(field_name) {
-                                match val {
-                                    Value::String(s) => {
-                                        // Try timezone-aware parsing first
-                                        let micros = if Self::is_iso_timestamp_tz(s) {
-                                            Self::parse_oracle_tz_format(s)
-                                                .or_else(|| Self::parse_timestamp_to_micros(s))
-                                        } else {
-                                            Self::parse_timestamp_to_micros(s)
-                                        };
-                                        
-                                        if let Some(micros) = micros {
-                                            values.push(micros);
-                                            validity.push(true);
-                                        } else {
-                                            values.push(0);
-                                            validity.push(false);
-                                        }
-                                    }
-                                    Value::Null => {
-                                        values.push(0);
-                                        validity.push(false);
-                                    }
-                                    _ => {
-                                        values.push(0);
-                                        validity.push(false);
-                                    }
-                                }
-                            } else {
-                                values.push(0);
-                                validity.push(false);
-                            }
-                        }
-                    }
-
-                    let buffer = Buffer::from(values);
-                    let validity: Validity = validity.into_iter().collect();
-                    PrimitiveArray::new(buffer, validity).into_array()
+                    let tz = self.column_time_zone(field_name);
+                    self.check_time_zones(field_name, tz.as_deref())?;
+                    let unit = self.timestamp_unit(field_name);
+                    let dtype = ColumnType::Timestamp { unit, tz }.to_dtype(Nullability::Nullable);
+                    tracing::debug!("Field '{}': timestamp unit {}", field_name, unit);
+
+                    let mut failures = ConversionFailures::new(field_name, format!("timestamp({})", unit));
+                    let (values, validity) = self.collect_column(field_name, true, &mut failures, |val| {
+                        Self::value_to_timestamp(val, unit)
+                    })?;
+                    failures.report(&self.rejects)?;
+                    self.report_precision_loss(field_name, unit)?;
+
+                    let storage = PrimitiveArray::new(Buffer::from(values), Self::validity_from_mask(validity, true));
+                    Self::extension_array(&dtype, storage.into_array())
+                }
+                DType::Extension(ref ext) if ext.id() == &*INTERVAL_ID => {
+                    // Handle INTERVAL types (struct {months, days, nanos})
+                    let mut failures = ConversionFailures::new(field_name, "interval".to_string());
+                    let (values, validity) = self.collect_column(field_name, true, &mut failures, |val| {
+                        let s = val.as_str()?;
+                        Self::parse_interval_day_to_second(s)
+                            .map(MonthDayNano::from_micros)
+                            .or_else(|| Self::parse_interval_year_to_month(s).map(MonthDayNano::from_months))
+                    })?;
+                    failures.report(&self.rejects)?;
+
+                    let values: Vec<Option<MonthDayNano>> = values
+                        .into_iter()
+                        .zip(validity)
+                        .map(|(value, valid)| valid.then_some(value))
+                        .collect();
+                    Self::extension_array(&dtype, interval::interval_storage(&values))
                 }
                 DType::Binary(_) => {
-                    // Handle Binary type (RAW/LONG RAW data)
-                    let values: Vec<Option<Vec<u8>>> = self.records.iter()
-                        .map(|record| {
-                            record.as_object()
-                                .and_then(|obj| obj.get(field_name))
-                                .and_then(|val| match val {
-                                    Value::String(s) => Self::hex_to_binary(s),
-                                    Value::Null => None,
-                                    _ => None,
-                                })
-                        })
+                    // Handle Binary type (RAW/LONG RAW/BLOB data)
+                    let decode = self.binary_decoder(field_name);
+                    let mut failures = ConversionFailures::new(field_name, "binary".to_string());
+                    let (values, validity) =
+                        self.collect_column(field_name, true, &mut failures, |val| decode(val.as_str()?))?;
+                    failures.report(&self.rejects)?;
+                    let values: Vec<Option<Vec<u8>>> = values
+                        .into_iter()
+                        .zip(validity)
+                        .map(|(value, valid)| valid.then_some(value))
                         .collect();
-
                     VarBinArray::from(values).into_array()
                 }
                 _ => {
@@ -782,8 +2106,15 @@ impl VortexWriter {
                     VarBinArray::from(values).into_array()
                 }
             };
+            let array = self.narrow_numbers(field_name, array)?;
+
+            fields.push((output_name.clone(), array.clone()));
+            self.push_time_zone_column(&mut fields, &output_columns, field_name, output_name, &array)?;
+        }
 
-            fields.push((field_name.as_str(), array));
+        self.rejects.flush()?;
+        if let (Some(path), count) = (&self.config.rejects_path, self.rejects.count()) {
+            tracing::info!("{} rejected values written to {:?}", count, path);
         }
 
         // Create StructArray
@@ -805,9 +2136,17 @@ impl VortexWriter {
             .await
             .context("Failed to write Vortex file to buffer")?;
 
-        // Write buffer to file with tokio
-        tokio::fs::write(output_path.as_ref(), buf).await
+        // Write buffer to a temporary file, then rename it: an interrupted run
+        // never leaves a partial file that `--resume` would take as complete
+        let output_path = output_path.as_ref();
+        let tmp = output_path.with_file_name(format!(
+            "{}.tmp",
+            output_path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default()
+        ));
+        tokio::fs::write(&tmp, buf).await
             .context("Failed to write buffer to file")?;
+        tokio::fs::rename(&tmp, output_path).await
+            .with_context(|| format!("Failed to rename {:?} to {:?}", tmp, output_path))?;
 
         tracing::info!("Successfully wrote {} records to Vortex file", self.records.len());
 
@@ -818,7 +2157,97 @@ impl VortexWriter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::columns::RenameCase;
+    use vortex_array::stream::ArrayStreamExt;
+    use vortex_array::ToCanonical;
     use vortex_dtype::datetime::{DATE_ID, TIMESTAMP_ID};
+    use vortex_file::OpenOptionsSessionExt;
+    use vortex_io::session::RuntimeSessionExt;
+
+    /// Column metadata as SQLcl reports it
+    fn columns(specs: &[(&str, &str)]) -> Vec<ColumnInfo> {
+        specs
+            .iter()
+            .map(|(name, oracle_type)| ColumnInfo { name: name.to_string(), oracle_type: oracle_type.to_string() })
+            .collect()
+    }
+
+    /// Flush the writer to a temporary file and read the file back
+    async fn flush_and_read(writer: &mut VortexWriter, name: &str) -> StructArray {
+        let path = std::env::temp_dir().join(format!("oracle2vortex_{}_{}.vortex", name, std::process::id()));
+        writer.flush(&path).await.unwrap();
+
+        let mut session = VortexSession::empty().with::<RuntimeSession>().with_tokio();
+        vortex_file::register_default_encodings(&mut session);
+        let array = session
+            .open_options()
+            .open_buffer(std::fs::read(&path).unwrap())
+            .unwrap()
+            .scan()
+            .unwrap()
+            .into_array_stream()
+            .unwrap()
+            .read_all()
+            .await
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        array.to_struct()
+    }
+
+    /// Column of a file read back by `flush_and_read`
+    fn field(array: &StructArray, name: &str) -> ArrayRef {
+        array.field_by_name(name).unwrap().clone()
+    }
+
+    /// Values of a primitive column, with nulls as `None`
+    fn primitive_values<T: vortex_dtype::NativePType>(column: &ArrayRef) -> Vec<Option<T>> {
+        let values = column.to_primitive();
+        (0..column.len())
+            .map(|i| column.is_valid(i).then(|| values.as_slice::<T>()[i]))
+            .collect()
+    }
+
+    /// Values of a Utf8 column, with nulls as `None`
+    fn utf8_values(column: &ArrayRef) -> Vec<Option<String>> {
+        (0..column.len())
+            .map(|i| column.scalar_at(i).as_utf8().value().map(|s| s.as_str().to_string()))
+            .collect()
+    }
+
+    /// Values of a binary column, with nulls as `None`
+    fn binary_values(column: &ArrayRef) -> Vec<Option<Vec<u8>>> {
+        (0..column.len())
+            .map(|i| column.scalar_at(i).as_binary().value().map(|bytes| bytes.to_vec()))
+            .collect()
+    }
+
+    /// Rows of a fixed-size binary column (FixedSizeList of U8), with nulls as `None`
+    fn fixed_binary_values(column: &ArrayRef) -> Vec<Option<Vec<u8>>> {
+        let lists = column.to_fixed_size_list();
+        (0..column.len())
+            .map(|i| {
+                column.is_valid(i).then(|| {
+                    primitive_values::<u8>(&lists.fixed_size_list_elements_at(i)).into_iter().flatten().collect()
+                })
+            })
+            .collect()
+    }
+
+    /// Unscaled values of a decimal column, with nulls as `None`
+    fn decimal_values(column: &ArrayRef) -> Vec<Option<i64>> {
+        (0..column.len())
+            .map(|i| column.scalar_at(i).as_decimal().decimal_value().and_then(|v| v.cast::<i64>()))
+            .collect()
+    }
+
+    /// Time unit and stored values of a timestamp column
+    fn timestamp_values(column: &ArrayRef) -> (TimeUnit, Vec<Option<i64>>) {
+        let DType::Extension(ext) = column.dtype() else {
+            panic!("expected a timestamp column, got {:?}", column.dtype());
+        };
+        let unit = TemporalMetadata::try_from(ext).unwrap().time_unit();
+        (unit, primitive_values::<i64>(column.to_extension().storage()))
+    }
 
     #[test]
     fn test_is_iso_date() {
@@ -872,10 +2301,65 @@ mod tests {
         assert_eq!(VortexWriter::parse_timestamp_to_micros("2024-03-15"), None); // Too short
     }
 
+    #[test]
+    fn test_parse_timestamp_to_nanos() {
+        assert_eq!(
+            VortexWriter::parse_timestamp_to_nanos("1970-01-01T00:00:01.123456789"),
+            Some(1_123_456_789)
+        );
+        // Microsecond parsing drops the last three digits
+        assert_eq!(
+            VortexWriter::parse_timestamp_to_micros("1970-01-01T00:00:01.123456789"),
+            Some(1_123_456)
+        );
+        assert_eq!(
+            VortexWriter::parse_timestamp_to_micros("2024-01-01T00:00:00"),
+            Some(1_704_067_200_000_000)
+        );
+        // Outside the i64 nanosecond range
+        assert_eq!(VortexWriter::parse_timestamp_to_nanos("2300-01-01T00:00:00"), None);
+    }
+
+    #[tokio::test]
+    async fn test_timestamp_unit_per_column() {
+        let mut writer = VortexWriter::new(WriterConfig::default());
+        writer.set_columns(&columns(&[("EVENT", "TIMESTAMP(9)"), ("LOGGED", "TIMESTAMP")])).unwrap();
+        writer.add_record(serde_json::json!({
+            "event": "2024-01-01T00:00:00.000000000",
+            "logged": "2024-01-01T00:00:00.123456700",
+            "plain": "2024-01-01T00:00:00.123456000",
+            "ancient": "1500-01-01T00:00:00.123456789"
+        })).await.unwrap();
+
+        // Declared scale wins over the observed digits
+        assert_eq!(writer.timestamp_unit("event"), TimeUnit::Nanoseconds);
+        // No declared scale: observed significant digits decide
+        assert_eq!(writer.timestamp_unit("logged"), TimeUnit::Nanoseconds);
+        assert_eq!(writer.timestamp_unit("plain"), TimeUnit::Microseconds);
+        // Out of the nanosecond range
+        assert_eq!(writer.timestamp_unit("ancient"), TimeUnit::Microseconds);
+        assert_eq!(VortexWriter::timestamp_scale("TIMESTAMP(3) WITH TIME ZONE"), Some(3));
+
+        writer.add_record(serde_json::json!({"event": "2024-01-01T00:00:00.000000001", "logged": null, "plain": "x"})).await.unwrap();
+        let array = flush_and_read(&mut writer, "ts_unit").await;
+        assert_eq!(
+            timestamp_values(&field(&array, "event")),
+            (TimeUnit::Nanoseconds, vec![Some(1_704_067_200_000_000_000), Some(1_704_067_200_000_000_001)])
+        );
+        assert_eq!(
+            timestamp_values(&field(&array, "logged")),
+            (TimeUnit::Nanoseconds, vec![Some(1_704_067_200_123_456_700), None])
+        );
+        assert_eq!(
+            timestamp_values(&field(&array, "plain")),
+            (TimeUnit::Microseconds, vec![Some(1_704_067_200_123_456), None])
+        );
+    }
+
     #[test]
     fn test_infer_dtype_date() {
         let value = serde_json::json!("2024-03-15");
-        let dtype = VortexWriter::infer_dtype(&value);
+        let dtype = VortexWriter::infer_dtype(&value, Heuristics::ALL);
         
         if let DType::Extension(ext) = &dtype {
             assert_eq!(ext.id(), &*DATE_ID);
@@ -887,7 +2371,7 @@ mod tests {
     #[test]
     fn test_infer_dtype_timestamp() {
         let value = serde_json::json!("2024-03-15T14:30:45");
-        let dtype = VortexWriter::infer_dtype(&value);
+        let dtype = VortexWriter::infer_dtype(&value, Heuristics::ALL);
         
         if let DType::Extension(ext) = &dtype {
             assert_eq!(ext.id(), &*TIMESTAMP_ID);
@@ -899,7 +2383,7 @@ mod tests {
     #[test]
     fn test_infer_dtype_string() {
         let value = serde_json::json!("just a string");
-        let dtype = VortexWriter::infer_dtype(&value);
+        let dtype = VortexWriter::infer_dtype(&value, Heuristics::ALL);
         
         assert!(matches!(dtype, DType::Utf8(_)));
     }
@@ -907,7 +2391,7 @@ mod tests {
     #[test]
     fn test_infer_dtype_number() {
         let value = serde_json::json!(42);
-        let dtype = VortexWriter::infer_dtype(&value);
+        let dtype = VortexWriter::infer_dtype(&value, Heuristics::ALL);
         
         assert!(matches!(dtype, DType::Primitive(PType::I64, _)));
     }
@@ -915,7 +2399,7 @@ mod tests {
     #[test]
     fn test_infer_dtype_float() {
         let value = serde_json::json!(42.5);
-        let dtype = VortexWriter::infer_dtype(&value);
+        let dtype = VortexWriter::infer_dtype(&value, Heuristics::ALL);
         
         assert!(matches!(dtype, DType::Primitive(PType::F64, _)));
     }
@@ -1003,14 +2487,14 @@ mod tests {
     #[test]
     fn test_infer_dtype_binary() {
         let value = serde_json::json!("DEADBEEF");
-        let dtype = VortexWriter::infer_dtype(&value);
+        let dtype = VortexWriter::infer_dtype(&value, Heuristics::ALL);
         assert!(matches!(dtype, DType::Binary(_)));
     }
 
     #[test]
     fn test_infer_dtype_timestamp_tz() {
         let value = serde_json::json!("2024-03-15T14:30:45 +02:00");
-        let dtype = VortexWriter::infer_dtype(&value);
+        let dtype = VortexWriter::infer_dtype(&value, Heuristics::ALL);
         
         if let DType::Extension(ext) = &dtype {
             assert_eq!(ext.id(), &*TIMESTAMP_ID);
@@ -1094,22 +2578,739 @@ mod tests {
     #[test]
     fn test_infer_dtype_interval_day_second() {
         let value = serde_json::json!("+05 12:00:00.123456");
-        let dtype = VortexWriter::infer_dtype(&value);
-        assert!(matches!(dtype, DType::Primitive(PType::I64, _)));
+        let dtype = VortexWriter::infer_dtype(&value, Heuristics::ALL);
+        assert_eq!(dtype, interval::interval_dtype(IntervalKind::DayToSecond, Nullability::Nullable));
     }
 
     #[test]
     fn test_infer_dtype_interval_year_month() {
         let value = serde_json::json!("+01-06");
-        let dtype = VortexWriter::infer_dtype(&value);
-        assert!(matches!(dtype, DType::Primitive(PType::I32, _)));
+        let dtype = VortexWriter::infer_dtype(&value, Heuristics::ALL);
+        assert_eq!(dtype, interval::interval_dtype(IntervalKind::YearToMonth, Nullability::Nullable));
     }
 
     #[test]
     fn test_infer_dtype_json() {
         let value = serde_json::json!(r#"{"key": "value"}"#);
-        let dtype = VortexWriter::infer_dtype(&value);
+        let dtype = VortexWriter::infer_dtype(&value, Heuristics::ALL);
         // JSON is kept as Utf8 for now
         assert!(matches!(dtype, DType::Utf8(_)));
     }
+
+    #[test]
+    fn test_parse_decimal() {
+        assert_eq!(VortexWriter::parse_decimal("123.45", 2), Some(12345));
+        assert_eq!(VortexWriter::parse_decimal("-0.5", 2), Some(-50));
+        assert_eq!(VortexWriter::parse_decimal("42", 3), Some(42000));
+        assert_eq!(VortexWriter::parse_decimal("1.5e2", 0), Some(150));
+        assert_eq!(VortexWriter::parse_decimal("1.20", 1), Some(12));
+        assert_eq!(VortexWriter::parse_decimal("1.234", 2), None); // Would lose a digit
+        assert_eq!(VortexWriter::parse_decimal("abc", 2), None);
+    }
+
+    #[test]
+    fn test_value_conversions() {
+        assert_eq!(VortexWriter::value_to_i64(&serde_json::json!("00123")), Some(123));
+        assert_eq!(VortexWriter::value_to_i64(&serde_json::json!(7.0)), Some(7));
+        assert_eq!(VortexWriter::value_to_i64(&serde_json::json!(7.5)), None);
+        assert_eq!(VortexWriter::value_to_bool(&serde_json::json!("TRUE")), Some(true));
+        assert_eq!(VortexWriter::value_to_decimal(&serde_json::json!(12345.6), 5, 1), None);
+        assert_eq!(VortexWriter::value_to_date(&serde_json::json!("2024-01-01T00:00:00")), Some(19723));
+        assert_eq!(
+            VortexWriter::value_to_timestamp(&serde_json::json!("1970-01-01T00:00:01"), TimeUnit::Milliseconds),
+            Some(1_000)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_flush_with_overrides() {
+        let schema = SchemaOverrides::load(
+            None,
+            &[
+                "zip=utf8".to_string(),
+                "amount=decimal(10,2) not null".to_string(),
+                "created=timestamp(ms)".to_string(),
+                "code=i32".to_string(),
+            ],
+        )
+        .unwrap();
+        let rejects = std::env::temp_dir().join(format!("oracle2vortex_overrides_{}.ndjson", std::process::id()));
+        let mut writer = VortexWriter::new(WriterConfig { schema, rejects_path: Some(rejects.clone()), ..Default::default() });
+        writer.add_record(serde_json::json!({"zip": "01234", "amount": 10.5, "created": "2024-01-01T00:00:00", "code": "x"})).await.unwrap();
+        writer.add_record(serde_json::json!({"zip": null, "amount": "3", "created": null, "code": 7})).await.unwrap();
+
+        let array = flush_and_read(&mut writer, "overrides").await;
+        // Leading zero kept as text
+        assert_eq!(field(&array, "zip").dtype(), &DType::Utf8(Nullability::Nullable));
+        assert_eq!(utf8_values(&field(&array, "zip")), vec![Some("01234".to_string()), None]);
+        // Scaled by 10^2, not nullable
+        let amount = field(&array, "amount");
+        assert_eq!(amount.dtype(), &DType::Decimal(DecimalDType::new(10, 2), Nullability::NonNullable));
+        assert_eq!(decimal_values(&amount), vec![Some(1050), Some(300)]);
+        assert_eq!(
+            timestamp_values(&field(&array, "created")),
+            (TimeUnit::Milliseconds, vec![Some(1_704_067_200_000), None])
+        );
+        // Unparseable code: null, and reported
+        assert_eq!(primitive_values::<i32>(&field(&array, "code")), vec![None, Some(7)]);
+        let lines: Vec<Value> = std::fs::read_to_string(&rejects)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0]["column"], "code");
+        assert_eq!(lines[0]["row"], 1);
+        assert_eq!(lines[0]["value"], "\"x\"");
+        std::fs::remove_file(&rejects).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_flush_not_null_override_rejects_null() {
+        let schema = SchemaOverrides::load(None, &["id=i64 not null".to_string()]).unwrap();
+        let mut writer = VortexWriter::new(WriterConfig { schema, ..Default::default() });
+        writer.add_record(serde_json::json!({"id": null})).await.unwrap();
+
+        let path = std::env::temp_dir().join(format!("oracle2vortex_not_null_{}.vortex", std::process::id()));
+        assert!(writer.flush(&path).await.is_err());
+    }
+
+    #[test]
+    fn test_infer_dtype_respects_heuristics() {
+        let hex = serde_json::json!("DEADBEEF");
+        let no_binary = Heuristics { binary: false, ..Heuristics::ALL };
+        assert!(matches!(VortexWriter::infer_dtype(&hex, no_binary), DType::Utf8(_)));
+
+        let date = serde_json::json!("2024-03-15");
+        assert!(matches!(VortexWriter::infer_dtype(&date, Heuristics::NONE), DType::Utf8(_)));
+
+        let policy = DetectionPolicy::from_args(&["dates".to_string()], false, false, &["SKU".to_string()]).unwrap();
+        assert!(matches!(VortexWriter::infer_dtype(&date, policy.for_column("created")), DType::Extension(_)));
+        assert!(matches!(VortexWriter::infer_dtype(&hex, policy.for_column("created")), DType::Utf8(_)));
+        assert_eq!(policy.for_column("sku"), Heuristics::NONE);
+    }
+
+    #[tokio::test]
+    async fn test_field_order_follows_column_metadata() {
+        let mut writer = VortexWriter::new(WriterConfig::default());
+        writer.set_columns(&columns(&[("ZIP", "VARCHAR2"), ("ID", "VARCHAR2"), ("NAME", "VARCHAR2")])).unwrap();
+        writer.add_record(serde_json::json!({"id": 1, "name": "a", "zip": "01234"})).await.unwrap();
+
+        assert_eq!(writer.output_fields(), vec!["zip", "id", "name"]);
+    }
+
+    #[tokio::test]
+    async fn test_union_schema_and_drift() {
+        let mut writer = VortexWriter::new(WriterConfig::default());
+        writer.start_batch();
+        writer.add_record(serde_json::json!({"id": 1})).await.unwrap();
+        writer.add_record(serde_json::json!({"id": 2, "note": "late"})).await.unwrap();
+        writer.start_batch();
+        writer.add_record(serde_json::json!({"id": 3, "extra": true})).await.unwrap();
+        assert_eq!(writer.output_fields(), vec!["id", "note", "extra"]);
+
+        let mut strict = VortexWriter::new(WriterConfig { schema_drift: SchemaDrift::Error, ..Default::default() });
+        strict.start_batch();
+        strict.add_record(serde_json::json!({"id": 1})).await.unwrap();
+        strict.start_batch();
+        assert!(strict.add_record(serde_json::json!({"id": 2, "extra": true})).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_lob_columns_by_oracle_type() {
+        let lobs = LobPolicy::from_args(LobMode::Skip, &["summary=truncate:4".to_string()]).unwrap();
+        let mut writer = VortexWriter::new(WriterConfig { lobs, ..Default::default() });
+        writer.set_columns(&columns(&[("ID", "NUMBER"), ("BODY", "CLOB"), ("SUMMARY", "CLOB")])).unwrap();
+        // Short first value: the column is still a LOB because of its type
+        writer.add_record(serde_json::json!({"id": 1, "body": "short", "summary": "abcdefgh"})).await.unwrap();
+
+        let fields = writer.apply_lob_policy(writer.output_fields()).unwrap();
+        assert_eq!(fields, vec!["id", "summary"]);
+        assert_eq!(writer.records[0]["summary"], "abcd");
+    }
+
+    #[tokio::test]
+    async fn test_blob_decoding_and_lob_lengths() {
+        let mut writer = VortexWriter::new(WriterConfig { verify_lob_lengths: true, ..Default::default() });
+        writer
+            .set_columns(&columns(&[("DOC", "BLOB"), ("NOTE", "CLOB"), ("DOC__LOB_LENGTH", "NUMBER"), ("NOTE__LOB_LENGTH", "NUMBER")]))
+            .unwrap();
+        writer.add_record(serde_json::json!({
+            "doc": "SGVsbG8=", "note": "abc", "doc__lob_length": 5, "note__lob_length": 10
+        })).await.unwrap();
+
+        // Not all values are hex: the BLOB column is decoded as base64
+        let decode = writer.binary_decoder("doc");
+        assert_eq!(decode("SGVsbG8="), Some(b"Hello".to_vec()));
+
+        let fields = writer.check_lob_lengths(writer.output_fields()).unwrap();
+        assert_eq!(fields, vec!["doc", "note"]);
+
+        // An excluded LOB column takes its length column with it
+        writer.config.layout = ColumnLayout::from_args(&[], &[], &[], &["NOTE".to_string()], RenameCase::Preserve).unwrap();
+        assert_eq!(writer.selected_fields(), vec!["doc", "doc__lob_length"]);
+        writer.config.layout = ColumnLayout::default();
+
+        // Base64 BLOBs are cut on the decoded bytes
+        writer.truncate_lob_column("doc", 4).unwrap();
+        assert_eq!(writer.records[0]["doc"], "SGVsbA==");
+        assert_eq!(decode("SGVsbA=="), Some(b"Hell".to_vec()));
+    }
+
+    #[tokio::test]
+    async fn test_external_lobs() {
+        let lobs = LobPolicy::from_args(LobMode::External(4), &[]).unwrap();
+        let mut writer = VortexWriter::new(WriterConfig { lobs, ..Default::default() });
+        writer.set_columns(&columns(&[("BODY", "CLOB"), ("NOTE", "CLOB")])).unwrap();
+        writer.add_record(serde_json::json!({"body": "abcdefgh", "note": "a"})).await.unwrap();
+        writer.add_record(serde_json::json!({"body": "abc", "note": "ab"})).await.unwrap();
+        writer.add_record(serde_json::json!({"body": "abcdefgh", "note": null})).await.unwrap();
+        writer.add_record(serde_json::json!({"body": null, "note": "abcd"})).await.unwrap();
+
+        let path = std::env::temp_dir().join(format!("oracle2vortex_external_{}.vortex", std::process::id()));
+        let lobs_dir = VortexWriter::lobs_dir(&path);
+        let array = flush_and_read(&mut writer, "external").await;
+
+        // One file per distinct value, none left under a temporary name
+        let mut files: Vec<String> = std::fs::read_dir(&lobs_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        files.sort();
+        let long = format!("{:x}", Sha256::digest(b"abcdefgh"));
+        let short = format!("{:x}", Sha256::digest(b"abc"));
+        let mut expected = vec![long.clone(), short.clone()];
+        expected.sort();
+        assert_eq!(files, expected);
+        assert_eq!(std::fs::read(lobs_dir.join(&long)).unwrap(), b"abcdefgh");
+
+        let body = field(&array, "body").to_struct();
+        assert_eq!(body.names().iter().map(|n| n.to_string()).collect::<Vec<_>>(), vec!["path", "size", "sha256"]);
+        let dir_name = lobs_dir.file_name().unwrap().to_string_lossy().to_string();
+        assert_eq!(
+            utf8_values(&field(&body, "path")),
+            vec![Some(format!("{}/{}", dir_name, long)), Some(format!("{}/{}", dir_name, short)), Some(format!("{}/{}", dir_name, long)), None]
+        );
+        assert_eq!(primitive_values::<u64>(&field(&body, "size"))[..3], [Some(8), Some(3), Some(8)]);
+        assert!(!field(&array, "body").is_valid(3));
+
+        // No value over the threshold: an ordinary text column
+        assert_eq!(
+            utf8_values(&field(&array, "note")),
+            vec![Some("a".to_string()), Some("ab".to_string()), None, Some("abcd".to_string())]
+        );
+
+        std::fs::remove_dir_all(&lobs_dir).unwrap();
+    }
+
+    #[test]
+    fn test_timezone_regions() {
+        assert!(VortexWriter::is_iso_timestamp_tz("2024-07-01T12:00:00.000000 Europe/Paris"));
+        assert!(!VortexWriter::is_iso_timestamp("2024-07-01T12:00:00.000000 Europe/Paris"));
+        assert_eq!(
+            VortexWriter::extract_timezone("2024-07-01T12:00:00.000000 America/Port-au-Prince"),
+            Some("America/Port-au-Prince".to_string())
+        );
+
+        // Paris is UTC+2 in summer and UTC+1 in winter
+        let summer = VortexWriter::parse_oracle_tz_format("2024-07-01T12:00:00 Europe/Paris").unwrap();
+        let winter = VortexWriter::parse_oracle_tz_format("2024-01-01T12:00:00 Europe/Paris").unwrap();
+        assert_eq!(summer, VortexWriter::parse_oracle_tz_format("2024-07-01T10:00:00Z").unwrap());
+        assert_eq!(winter, VortexWriter::parse_oracle_tz_format("2024-01-01T11:00:00Z").unwrap());
+        assert_eq!(VortexWriter::parse_oracle_tz_format("2024-01-01T12:00:00 Nowhere/City"), None);
+    }
+
+    #[tokio::test]
+    async fn test_unresolvable_time_zone_is_rejected() {
+        let mut writer = VortexWriter::new(WriterConfig::default());
+        writer.add_record(serde_json::json!({"at": "2024-01-01T12:00:00.000000 Europe/Paris"})).await.unwrap();
+        writer.add_record(serde_json::json!({"at": "2024-01-01T12:00:00.000000 Nowhere/City"})).await.unwrap();
+
+        let array = flush_and_read(&mut writer, "unknown_zone").await;
+        let at = field(&array, "at").to_extension().storage().clone();
+        assert!(at.is_valid(0));
+        // Not read as a UTC time: stored as null and reported
+        assert!(!at.is_valid(1));
+    }
+
+    #[tokio::test]
+    async fn test_column_time_zone_and_companion() {
+        let mut writer = VortexWriter::new(WriterConfig { time_zone_columns: true, ..Default::default() });
+        writer.add_record(serde_json::json!({"at": "2024-07-01T12:00:00.000000 Europe/Paris"})).await.unwrap();
+        writer.add_record(serde_json::json!({"at": "2024-07-01T12:00:00.000000 Europe/Paris"})).await.unwrap();
+        assert_eq!(writer.column_time_zone("at"), Some("Europe/Paris".to_string()));
+
+        writer.add_record(serde_json::json!({"at": "2024-07-01T12:00:00.000000 +05:30"})).await.unwrap();
+        writer.add_record(serde_json::json!({"at": null})).await.unwrap();
+        assert_eq!(writer.column_time_zone("at"), Some("UTC".to_string()));
+
+        let array = flush_and_read(&mut writer, "tz").await;
+        let at = field(&array, "at");
+        assert!(matches!(at.dtype(), DType::Extension(ext) if ext.id() == &*TIMESTAMP_ID));
+        // 12:00 in Paris (UTC+2) and in UTC+05:30
+        let paris = 1_719_828_000_000_000i64;
+        let kolkata = 1_719_815_400_000_000i64;
+        assert_eq!(
+            primitive_values::<i64>(at.to_extension().storage()),
+            vec![Some(paris), Some(paris), Some(kolkata), None]
+        );
+        assert_eq!(
+            utf8_values(&field(&array, "at_tz")),
+            vec![Some("Europe/Paris".to_string()), Some("Europe/Paris".to_string()), Some("+05:30".to_string()), None]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_oracle_date_policy() {
+        let is_date = |dtype: Option<DType>| matches!(dtype, Some(DType::Extension(ext)) if ext.id() == &*DATE_ID);
+        let is_timestamp = |dtype: Option<DType>| matches!(dtype, Some(DType::Extension(ext)) if ext.id() == &*TIMESTAMP_ID);
+
+        let mut writer = VortexWriter::new(WriterConfig { oracle_date_as: OracleDateAs::Auto, ..Default::default() });
+        writer.add_record(serde_json::json!({"hired": "2024-01-15T00:00:00", "seen": "2024-01-15T08:30:00", "at": "2024-01-15T00:00:00.000000"})).await.unwrap();
+        writer.add_record(serde_json::json!({"hired": null, "seen": "2024-01-16T00:00:00", "at": null})).await.unwrap();
+
+        let inferred = |writer: &VortexWriter, field: &str| {
+            let value = writer.records[0].get(field).unwrap();
+            VortexWriter::infer_dtype(value, Heuristics::ALL)
+        };
+        assert!(is_date(writer.oracle_date_dtype("hired", &inferred(&writer, "hired"), Heuristics::ALL).unwrap()));
+        assert!(is_timestamp(writer.oracle_date_dtype("seen", &inferred(&writer, "seen"), Heuristics::ALL).unwrap()));
+        // A fraction means TIMESTAMP, not DATE
+        assert!(writer.oracle_date_dtype("at", &inferred(&writer, "at"), Heuristics::ALL).unwrap().is_none());
+
+        // The column metadata decides when present, whatever was inferred
+        writer.set_columns(&columns(&[("HIRED", "DATE")])).unwrap();
+        writer.config.oracle_date_as = OracleDateAs::Date;
+        assert!(is_date(writer.oracle_date_dtype("hired", &DType::Utf8(Nullability::Nullable), Heuristics::ALL).unwrap()));
+        assert!(writer.oracle_date_dtype("hired", &DType::Utf8(Nullability::Nullable), Heuristics::NONE).unwrap().is_none());
+
+        let array = flush_and_read(&mut writer, "dates").await;
+        let hired = field(&array, "hired");
+        assert!(is_date(Some(hired.dtype().clone())));
+        // Days since 1970-01-01
+        assert_eq!(primitive_values::<i32>(hired.to_extension().storage()), vec![Some(19_737), None]);
+        // --oracle-date-as date drops the time of day
+        let seen = field(&array, "seen");
+        assert_eq!(primitive_values::<i32>(seen.to_extension().storage()), vec![Some(19_737), Some(19_738)]);
+        assert!(is_timestamp(Some(field(&array, "at").dtype().clone())));
+    }
+
+    #[tokio::test]
+    async fn test_flush_intervals() {
+        let mut writer = VortexWriter::new(WriterConfig::default());
+        writer.add_record(serde_json::json!({"wait": "+01 02:00:00.000000", "term": "+01-06"})).await.unwrap();
+        writer.add_record(serde_json::json!({"wait": null, "term": "-00-01"})).await.unwrap();
+
+        let array = flush_and_read(&mut writer, "intervals").await;
+        let intervals = |name: &str| -> Vec<Option<MonthDayNano>> {
+            let column = field(&array, name);
+            assert!(matches!(column.dtype(), DType::Extension(ext) if ext.id() == &*INTERVAL_ID));
+            let storage = column.to_extension().storage().to_struct();
+            let months = primitive_values::<i32>(&field(&storage, "months"));
+            let days = primitive_values::<i32>(&field(&storage, "days"));
+            let nanos = primitive_values::<i64>(&field(&storage, "nanos"));
+            (0..storage.len())
+                .map(|i| {
+                    storage.is_valid(i).then(|| MonthDayNano {
+                        months: months[i].unwrap(),
+                        days: days[i].unwrap(),
+                        nanos: nanos[i].unwrap(),
+                    })
+                })
+                .collect()
+        };
+        assert_eq!(intervals("wait"), vec![Some(MonthDayNano { months: 0, days: 1, nanos: 7_200_000_000_000 }), None]);
+        assert_eq!(
+            intervals("term"),
+            vec![Some(MonthDayNano::from_months(18)), Some(MonthDayNano::from_months(-1))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_flush_vectors() {
+        let mut writer = VortexWriter::new(WriterConfig::default());
+        writer
+            .set_columns(&columns(&[("EMBEDDING", "VECTOR(3, FLOAT32)"), ("CODES", "VECTOR(*, INT8)"), ("SCORES", "VECTOR")]))
+            .unwrap();
+        writer.add_record(serde_json::json!({"embedding": [0.5, -1.0, 2.0], "codes": "[1,2]", "scores": [0.1, 1e300]})).await.unwrap();
+        writer.add_record(serde_json::json!({"embedding": "[1.0E+000,2.0E+000,3.0E+000]", "codes": null, "scores": null})).await.unwrap();
+        // Wrong dimension: reported and stored as null
+        writer.add_record(serde_json::json!({"embedding": [1.0], "codes": [3, 4], "scores": [1, -2]})).await.unwrap();
+
+        let mut failures = ConversionFailures::new("codes", "vector".to_string());
+        let codes = writer.build_vector_column("codes", None, VectorElement::I8, true, &mut failures).unwrap();
+        assert_eq!(codes.dtype(), &vector::vector_dtype(2, VectorElement::I8, Nullability::Nullable));
+        assert!(failures.rejected.is_empty());
+
+        let mut failures = ConversionFailures::new("embedding", "vector".to_string());
+        let embedding = writer.build_vector_column("embedding", Some(3), VectorElement::F32, true, &mut failures).unwrap();
+        assert_eq!(embedding.len(), 3);
+        assert_eq!(failures.rejected.len(), 1);
+
+        let array = flush_and_read(&mut writer, "vectors").await;
+        let vectors = |name: &str| -> Vec<Option<ArrayRef>> {
+            let column = field(&array, name);
+            let lists = column.to_fixed_size_list();
+            (0..column.len()).map(|i| column.is_valid(i).then(|| lists.fixed_size_list_elements_at(i))).collect()
+        };
+        assert_eq!(field(&array, "embedding").dtype(), &vector::vector_dtype(3, VectorElement::F32, Nullability::Nullable));
+        let embedding = vectors("embedding");
+        assert_eq!(primitive_values::<f32>(embedding[0].as_ref().unwrap()), vec![Some(0.5), Some(-1.0), Some(2.0)]);
+        assert_eq!(primitive_values::<f32>(embedding[1].as_ref().unwrap()), vec![Some(1.0), Some(2.0), Some(3.0)]);
+        assert!(embedding[2].is_none());
+        let codes = vectors("codes");
+        assert_eq!(primitive_values::<i8>(codes[0].as_ref().unwrap()), vec![Some(1), Some(2)]);
+        assert!(codes[1].is_none());
+        assert_eq!(primitive_values::<i8>(codes[2].as_ref().unwrap()), vec![Some(3), Some(4)]);
+        // Bare VECTOR (format not reported): F64, whatever the stored format
+        assert_eq!(field(&array, "scores").dtype(), &vector::vector_dtype(2, VectorElement::F64, Nullability::Nullable));
+        let scores = vectors("scores");
+        assert_eq!(primitive_values::<f64>(scores[0].as_ref().unwrap()), vec![Some(0.1), Some(1e300)]);
+        assert_eq!(primitive_values::<f64>(scores[2].as_ref().unwrap()), vec![Some(1.0), Some(-2.0)]);
+    }
+
+    #[tokio::test]
+    async fn test_flush_nested_json() {
+        let mut writer = VortexWriter::new(WriterConfig { nested_json: true, ..Default::default() });
+        writer.add_record(serde_json::json!({"doc": {"id": 1, "tags": ["a", "b"]}, "raw": "{\"k\": [1, 2.5]}", "note": "plain"})).await.unwrap();
+        writer.add_record(serde_json::json!({"doc": null, "raw": "[]", "note": "{\"not\": \"all\"}"})).await.unwrap();
+
+        assert!(writer.json_documents("doc").is_some());
+        assert!(writer.json_documents("raw").is_some());
+        // Not every value is a document
+        assert!(writer.json_documents("note").is_none());
+
+        // Objects mixed with arrays fall back to JSON text
+        let raw = VortexWriter::build_nested_json_column("raw", &writer.json_documents("raw").unwrap());
+        assert_eq!(raw.dtype(), &DType::Utf8(Nullability::Nullable));
+        let doc = VortexWriter::build_nested_json_column("doc", &writer.json_documents("doc").unwrap());
+        assert!(matches!(doc.dtype(), DType::Struct(..)));
+
+        let array = flush_and_read(&mut writer, "nested").await;
+        let doc = field(&array, "doc").to_struct();
+        assert_eq!(doc.names().iter().map(|n| n.to_string()).collect::<Vec<_>>(), vec!["id", "tags"]);
+        assert!(doc.is_valid(0) && !doc.is_valid(1));
+        assert_eq!(primitive_values::<i64>(&field(&doc, "id")), vec![Some(1), None]);
+        let tags = field(&doc, "tags").to_listview();
+        assert_eq!(utf8_values(&tags.list_elements_at(0)), vec![Some("a".to_string()), Some("b".to_string())]);
+        assert_eq!(
+            utf8_values(&field(&array, "raw")),
+            vec![Some("{\"k\":[1,2.5]}".to_string()), Some("[]".to_string())]
+        );
+        assert_eq!(
+            utf8_values(&field(&array, "note")),
+            vec![Some("plain".to_string()), Some("{\"not\": \"all\"}".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_flush_heterogeneous_json() {
+        let mut writer = VortexWriter::new(WriterConfig { nested_json: true, ..Default::default() });
+        writer.set_columns(&columns(&[("DOC", "JSON"), ("MIXED", "JSON")])).unwrap();
+        writer.add_record(serde_json::json!({"doc": {"id": 1, "v": {"a": 1}}, "mixed": {"k": 1}})).await.unwrap();
+        writer.add_record(serde_json::json!({"doc": {"id": 2, "v": 3}, "mixed": [1, 2]})).await.unwrap();
+        writer.add_record(serde_json::json!({"doc": {"id": 3, "v": null}, "mixed": null})).await.unwrap();
+
+        let array = flush_and_read(&mut writer, "heterogeneous").await;
+        // An object and a number at `v`: only that field is JSON text
+        let doc = field(&array, "doc").to_struct();
+        assert_eq!(primitive_values::<i64>(&field(&doc, "id")), vec![Some(1), Some(2), Some(3)]);
+        assert_eq!(field(&doc, "v").dtype(), &DType::Utf8(Nullability::Nullable));
+        assert_eq!(utf8_values(&field(&doc, "v")), vec![Some("{\"a\":1}".to_string()), Some("3".to_string()), None]);
+        // Documents with no common shape: the whole column is JSON text
+        assert_eq!(field(&array, "mixed").dtype(), &DType::Utf8(Nullability::Nullable));
+        assert_eq!(
+            utf8_values(&field(&array, "mixed")),
+            vec![Some("{\"k\":1}".to_string()), Some("[1,2]".to_string()), None]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_flush_object_types() {
+        let mut types = TypeCatalog::default();
+        types.add_rows(&[
+            serde_json::json!({"type_owner": "HR", "type_name": "POINT_T", "kind": "OBJECT", "attr_name": "X", "elem_type": "NUMBER", "precision": 9, "scale": 3}),
+            serde_json::json!({"type_owner": "HR", "type_name": "POINT_T", "kind": "OBJECT", "attr_name": "Y", "elem_type": "NUMBER", "precision": 9, "scale": 3}),
+            // No precision: text, so that ids above 2^53 keep every digit
+            serde_json::json!({"type_owner": "HR", "type_name": "POINT_T", "kind": "OBJECT", "attr_name": "ID", "elem_type": "NUMBER"}),
+            serde_json::json!({"type_owner": "HR", "type_name": "PATH_T", "kind": "COLLECTION", "elem_owner": "HR", "elem_type": "POINT_T"}),
+        ]);
+
+        let mut writer = VortexWriter::new(WriterConfig::default());
+        writer.set_type_catalog(types);
+        writer.set_columns(&columns(&[("ROUTE", "HR.PATH_T")])).unwrap();
+        writer.add_record(serde_json::json!({"route": "HR.PATH_T(HR.POINT_T(1, 2.5, 9007199254740993), HR.POINT_T(-.5, NULL, NULL))"})).await.unwrap();
+        writer.add_record(serde_json::json!({"route": null})).await.unwrap();
+
+        let array = flush_and_read(&mut writer, "objects").await;
+        let route = field(&array, "route");
+        let DType::List(element, Nullability::Nullable) = route.dtype() else {
+            panic!("expected a list, got {}", route.dtype());
+        };
+        let DType::Struct(fields, _) = element.as_ref() else {
+            panic!("expected a struct element, got {}", element);
+        };
+        assert_eq!(fields.names().iter().map(|n| n.to_string()).collect::<Vec<_>>(), vec!["X", "Y", "ID"]);
+
+        assert!(route.is_valid(0) && !route.is_valid(1));
+        let points = route.to_listview().list_elements_at(0).to_struct();
+        assert_eq!(primitive_values::<f64>(&field(&points, "X")), vec![Some(1.0), Some(-0.5)]);
+        assert_eq!(primitive_values::<f64>(&field(&points, "Y")), vec![Some(2.5), None]);
+        assert_eq!(utf8_values(&field(&points, "ID")), vec![Some("9007199254740993".to_string()), None]);
+    }
+
+    #[tokio::test]
+    async fn test_flush_geometries() {
+        let mut writer = VortexWriter::new(WriterConfig::default());
+        writer.set_columns(&columns(&[("SHAPE", "MDSYS.SDO_GEOMETRY")])).unwrap();
+        writer.add_record(serde_json::json!({"shape": "MDSYS.SDO_GEOMETRY(2001, 4326, MDSYS.SDO_POINT_TYPE(2.35, 48.85, NULL), NULL, NULL)"})).await.unwrap();
+        writer.add_record(serde_json::json!({"shape": null})).await.unwrap();
+
+        let array = writer.build_geometry_column("shape").unwrap();
+        assert_eq!(array.dtype(), &geometry::wkb_dtype(Some(4326)));
+
+        let array = flush_and_read(&mut writer, "geometry").await;
+        let shape = field(&array, "shape");
+        assert_eq!(shape.dtype(), &geometry::wkb_dtype(Some(4326)));
+        // Little-endian WKB point
+        let mut point = vec![1u8];
+        point.extend_from_slice(&1u32.to_le_bytes());
+        point.extend_from_slice(&2.35f64.to_le_bytes());
+        point.extend_from_slice(&48.85f64.to_le_bytes());
+        assert_eq!(binary_values(shape.to_extension().storage()), vec![Some(point), None]);
+    }
+
+    #[tokio::test]
+    async fn test_boolean_and_flag_columns() {
+        let bool_flags = BoolFlags::from_args(Some(crate::flags::DEFAULT_FLAG_PAIRS), &["OPEN=O/C".to_string()]).unwrap();
+        let mut writer = VortexWriter::new(WriterConfig { bool_flags, ..Default::default() });
+        writer.set_columns(&columns(&[("ENABLED", "BOOLEAN")])).unwrap();
+        writer.add_record(serde_json::json!({"enabled": "TRUE", "active": "Y", "status": "Y", "grade": "A", "open": "O", "code": "YES"})).await.unwrap();
+        writer.add_record(serde_json::json!({"enabled": false, "active": "n", "status": "X", "grade": "B", "open": "x", "code": "N"})).await.unwrap();
+        writer.add_record(serde_json::json!({"enabled": null, "active": null, "status": "N", "grade": null, "open": "c", "code": null})).await.unwrap();
+
+        // The global rule needs every value to follow it; a column rule is lenient
+        assert!(writer.flag_rule("active").is_some());
+        assert!(writer.flag_rule("status").is_none());
+        assert!(writer.flag_rule("grade").is_none());
+        assert!(writer.flag_rule("code").is_none());
+        assert!(writer.flag_rule("open").is_some());
+
+        let array = flush_and_read(&mut writer, "flags").await;
+        let bools = |name: &str| -> Vec<Option<bool>> {
+            let column = field(&array, name);
+            assert_eq!(column.dtype(), &DType::Bool(Nullability::Nullable));
+            (0..column.len()).map(|i| column.scalar_at(i).as_bool().value()).collect()
+        };
+        assert_eq!(bools("enabled"), vec![Some(true), Some(false), None]);
+        assert_eq!(bools("active"), vec![Some(true), Some(false), None]);
+        // Not a flag value: reported and stored as null
+        assert_eq!(bools("open"), vec![Some(true), None, Some(false)]);
+        assert_eq!(
+            utf8_values(&field(&array, "status")),
+            vec![Some("Y".to_string()), Some("X".to_string()), Some("N".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_narrow_numbers() {
+        let mut writer = VortexWriter::new(WriterConfig::default());
+        writer
+            .set_columns(&columns(&[("QTY", "NUMBER(4,0)"), ("RATIO", "BINARY_FLOAT"), ("AMOUNT", "NUMBER(10,2)")]))
+            .unwrap();
+        writer.add_record(serde_json::json!({"qty": 12, "ratio": 0.5, "amount": 10, "code": 100000, "small": -5})).await.unwrap();
+        writer.add_record(serde_json::json!({"qty": null, "ratio": 1.25, "amount": 11, "code": 7, "small": 100})).await.unwrap();
+
+        assert_eq!(writer.numeric_ptype("qty", PType::I64), Some(PType::I16));
+        assert_eq!(writer.numeric_ptype("ratio", PType::F64), Some(PType::F32));
+        assert_eq!(writer.numeric_ptype("amount", PType::I64), None);
+        // No declared precision: I64 unless narrowing by the observed range
+        assert_eq!(writer.numeric_ptype("code", PType::I64), None);
+        writer.config.narrow_by_values = true;
+        assert_eq!(writer.numeric_ptype("code", PType::I64), Some(PType::I32));
+        assert_eq!(writer.numeric_ptype("small", PType::I64), Some(PType::I8));
+        writer.config.narrow_by_values = false;
+
+        let qty = PrimitiveArray::new(Buffer::from(vec![12i64, 0]), Validity::from_iter([true, false])).into_array();
+        assert_eq!(writer.narrow_numbers("qty", qty).unwrap().dtype(), &DType::Primitive(PType::I16, Nullability::Nullable));
+
+        writer.config.wide_numbers = true;
+        let qty = PrimitiveArray::new(Buffer::from(vec![12i64]), Validity::NonNullable).into_array();
+        assert_eq!(writer.narrow_numbers("qty", qty).unwrap().dtype(), &DType::Primitive(PType::I64, Nullability::NonNullable));
+
+        writer.config.wide_numbers = false;
+        let array = flush_and_read(&mut writer, "narrow").await;
+        let ptype = |name: &str| match field(&array, name).dtype() {
+            DType::Primitive(ptype, _) => *ptype,
+            other => panic!("expected a primitive column, got {}", other),
+        };
+        assert_eq!(ptype("qty"), PType::I16);
+        assert_eq!(ptype("ratio"), PType::F32);
+        assert_eq!(ptype("amount"), PType::I64);
+        assert_eq!(ptype("code"), PType::I64);
+        assert_eq!(primitive_values::<i16>(&field(&array, "qty")), vec![Some(12), None]);
+        assert_eq!(primitive_values::<f32>(&field(&array, "ratio")), vec![Some(0.5), Some(1.25)]);
+        assert_eq!(primitive_values::<i64>(&field(&array, "code")), vec![Some(100_000), Some(7)]);
+    }
+
+    #[tokio::test]
+    async fn test_raw_columns() {
+        let mut writer = VortexWriter::new(WriterConfig { raw16_as: Raw16As::Uuid, ..Default::default() });
+        writer.set_columns(&columns(&[("ID", "RAW(16)"), ("HASH", "RAW"), ("CODE", "RAW(4)")])).unwrap();
+        writer.add_record(serde_json::json!({"id": "000102030405060708090A0B0C0D0E0F", "hash": "DEADBEEFCAFE", "code": "DEADBEEF"})).await.unwrap();
+        writer.add_record(serde_json::json!({"id": null, "hash": "0123456789AB", "code": "BEEF"})).await.unwrap();
+
+        assert_eq!(writer.raw_size("hash"), Some(6));
+        assert_eq!(writer.build_raw_column("id", 16).unwrap().dtype(), &raw::uuid_dtype());
+        // Wrong length: reported and stored as null
+        let code = writer.build_raw_column("code", 4).unwrap();
+        assert_eq!(code.dtype(), &raw::fixed_binary_dtype(4, Nullability::Nullable));
+        assert!(!code.is_valid(1));
+
+        let array = flush_and_read(&mut writer, "raw").await;
+        let id = field(&array, "id");
+        assert_eq!(id.dtype(), &raw::uuid_dtype());
+        assert_eq!(fixed_binary_values(id.to_extension().storage()), vec![Some((0u8..16).collect()), None]);
+        assert_eq!(
+            fixed_binary_values(&field(&array, "hash")),
+            vec![Some(vec![0xDE, 0xAD, 0xBE, 0xEF, 0xCA, 0xFE]), Some(vec![0x01, 0x23, 0x45, 0x67, 0x89, 0xAB])]
+        );
+        assert_eq!(fixed_binary_values(&field(&array, "code")), vec![Some(vec![0xDE, 0xAD, 0xBE, 0xEF]), None]);
+
+        writer.config.raw16_as = Raw16As::Text;
+        let text = writer.build_raw_column("id", 16).unwrap();
+        assert_eq!(text.dtype(), &DType::Utf8(Nullability::Nullable));
+    }
+
+    #[tokio::test]
+    async fn test_special_floats() {
+        assert!(VortexWriter::value_to_f64(&serde_json::json!("NaN")).unwrap().is_nan());
+        assert_eq!(VortexWriter::value_to_f64(&serde_json::json!("-Inf")), Some(f64::NEG_INFINITY));
+        assert_eq!(VortexWriter::value_to_f64(&serde_json::json!("Infinity")), Some(f64::INFINITY));
+        assert_eq!(VortexWriter::value_to_f64(&serde_json::json!("n/a")), None);
+
+        let mut writer = VortexWriter::new(WriterConfig::default());
+        writer.set_columns(&columns(&[("MEASURE", "BINARY_DOUBLE")])).unwrap();
+        writer.add_record(serde_json::json!({"measure": "Nan", "ratio": "Inf", "label": "NaN", "status": "NaN"})).await.unwrap();
+        writer.add_record(serde_json::json!({"measure": 1.5, "ratio": 2, "label": "text", "status": "NaN"})).await.unwrap();
+        writer.add_record(serde_json::json!({"measure": "oops", "ratio": null, "label": null, "status": null})).await.unwrap();
+
+        // Declared BINARY_DOUBLE, or numbers mixed with tokens; tokens alone stay text
+        assert!(writer.is_float_column("measure"));
+        assert!(writer.is_float_column("ratio"));
+        assert!(!writer.is_float_column("label"));
+        assert!(!writer.is_float_column("status"));
+
+        // No detection: only the declared type counts
+        let mut strings_only = VortexWriter::new(WriterConfig {
+            detection: DetectionPolicy::from_args(&[], false, true, &[]).unwrap(),
+            ..Default::default()
+        });
+        strings_only.set_columns(&columns(&[("MEASURE", "BINARY_DOUBLE")])).unwrap();
+        strings_only.records = writer.records.clone();
+        assert!(strings_only.is_float_column("measure"));
+        assert!(!strings_only.is_float_column("ratio"));
+        let mut exempt = VortexWriter::new(WriterConfig {
+            detection: DetectionPolicy::from_args(&[], false, false, &["RATIO".to_string()]).unwrap(),
+            ..Default::default()
+        });
+        exempt.records = writer.records.clone();
+        assert!(!exempt.is_float_column("ratio"));
+
+        let array = flush_and_read(&mut writer, "special_floats").await;
+        let measure = primitive_values::<f64>(&field(&array, "measure"));
+        assert!(measure[0].unwrap().is_nan());
+        // Not a number: reported and stored as null
+        assert_eq!(measure[1..], [Some(1.5), None]);
+        assert_eq!(primitive_values::<f64>(&field(&array, "ratio")), vec![Some(f64::INFINITY), Some(2.0), None]);
+        assert_eq!(utf8_values(&field(&array, "label")), vec![Some("NaN".to_string()), Some("text".to_string()), None]);
+        assert_eq!(utf8_values(&field(&array, "status")), vec![Some("NaN".to_string()), Some("NaN".to_string()), None]);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_file_and_limit() {
+        let dir = std::env::temp_dir();
+        let rejects = dir.join(format!("oracle2vortex_writer_rejects_{}.ndjson", std::process::id()));
+        let output = dir.join(format!("oracle2vortex_writer_rejects_{}.vortex", std::process::id()));
+        let config = WriterConfig { rejects_path: Some(rejects.clone()), ..Default::default() };
+
+        let mut writer = VortexWriter::new(config.clone());
+        writer.add_record(serde_json::json!({"qty": 1, "flag": true})).await.unwrap();
+        writer.add_record(serde_json::json!({"qty": 2.5, "flag": "maybe"})).await.unwrap();
+        writer.add_record(serde_json::json!({"qty": "n/a", "flag": false})).await.unwrap();
+        // A fractional value after integers makes the column F64 instead of truncating it
+        assert!(writer.has_fractional_numbers("qty"));
+        writer.flush(&output).await.unwrap();
+
+        let lines: Vec<Value> = std::fs::read_to_string(&rejects)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["column"], "flag");
+        assert_eq!(lines[0]["row"], 2);
+        assert_eq!(lines[1]["column"], "qty");
+        assert_eq!(lines[1]["row"], 3);
+        assert_eq!(lines[1]["value"], "\"n/a\"");
+        assert_eq!(lines[1]["target_type"], "f64");
+
+        let mut writer = VortexWriter::new(WriterConfig { max_rejects: Some(1), ..config });
+        writer.add_record(serde_json::json!({"qty": 3})).await.unwrap();
+        writer.add_record(serde_json::json!({"qty": "a"})).await.unwrap();
+        writer.add_record(serde_json::json!({"qty": "b"})).await.unwrap();
+        assert!(writer.flush(&output).await.is_err());
+
+        std::fs::remove_file(&rejects).unwrap();
+        std::fs::remove_file(&output).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_strict_mode() {
+        let path = std::env::temp_dir().join(format!("oracle2vortex_strict_{}.vortex", std::process::id()));
+        let strict = WriterConfig { strict: true, ..Default::default() };
+        let flush_error = |records: Vec<Value>, config: WriterConfig| {
+            let path = path.clone();
+            async move {
+                let mut writer = VortexWriter::new(config);
+                for record in records {
+                    writer.add_record(record).await.unwrap();
+                }
+                writer.flush(&path).await.err().map(|e| e.to_string())
+            }
+        };
+
+        // Unparseable value, named by column and row
+        let error = flush_error(vec![serde_json::json!({"qty": 1}), serde_json::json!({"qty": "n/a"})], strict.clone()).await;
+        assert_eq!(error.as_deref(), Some("Column 'qty', row 2: \"n/a\" cannot be converted to i64 (--strict)"));
+
+        // Integer beyond the F64 mantissa
+        let error = flush_error(vec![serde_json::json!({"x": 0.5}), serde_json::json!({"x": 9007199254740993u64})], strict.clone()).await;
+        assert!(error.unwrap().contains("row 2: 9007199254740993 loses precision as f64"));
+
+        // Mixed offsets are normalised to UTC
+        let records = vec![
+            serde_json::json!({"at": "2024-03-15T14:30:45.000000+02:00"}),
+            serde_json::json!({"at": "2024-03-15T14:30:45.000000Z"}),
+        ];
+        let error = flush_error(records.clone(), strict.clone()).await;
+        assert!(error.unwrap().starts_with("Column 'at', row 1: 2024-03-15T14:30:45.000000+02:00 is normalised to UTC"));
+        let keep_zones = WriterConfig { time_zone_columns: true, ..strict.clone() };
+        assert_eq!(flush_error(records.clone(), keep_zones).await, None);
+        assert_eq!(flush_error(records, WriterConfig::default()).await, None);
+
+        // Time of day dropped from an Oracle DATE
+        let dates = WriterConfig { oracle_date_as: OracleDateAs::Date, ..strict.clone() };
+        let error = flush_error(vec![serde_json::json!({"hired": "2024-03-15T09:00:00"})], dates).await;
+        assert!(error.unwrap().contains("row 1: 2024-03-15T09:00:00 has a time of day"));
+
+        // Truncated LOB, unless the column is excluded from the output
+        let truncated = vec![serde_json::json!({"id": 1, "note": "abc", "note__lob_length": 10})];
+        let verify = WriterConfig { verify_lob_lengths: true, ..strict };
+        assert!(flush_error(truncated.clone(), verify.clone()).await.unwrap().contains("'note'"));
+        let layout = ColumnLayout::from_args(&[], &[], &[], &["NOTE".to_string()], RenameCase::Preserve).unwrap();
+        assert_eq!(flush_error(truncated, WriterConfig { layout, ..verify }).await, None);
+
+        let _ = std::fs::remove_file(&path);
+    }
 }